@@ -21,8 +21,12 @@ pub mod attribute;
 pub use attribute::{Attr, Attrs};
 mod children;
 pub use children::*;
+mod control_flow;
+pub use control_flow::*;
 mod element;
 pub use element::*;
+mod fragment;
+pub use fragment::*;
 mod ident;
 pub use ident::*;
 mod tag;
@@ -1,22 +1,30 @@
+pub mod class_list;
 pub mod directive;
 pub mod kv;
 pub mod selector;
 pub mod spread_attrs;
+pub mod style_list;
 
+use proc_macro2::Span;
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseStream},
     Token,
 };
 
-use self::{directive::Directive, kv::KvAttr, spread_attrs::SpreadAttr};
-use crate::{error_ext::ResultExt, parse::rollback_err};
+use self::{
+    class_list::ClassList, directive::Directive, kv::KvAttr, spread_attrs::SpreadAttr,
+    style_list::StyleList,
+};
+use crate::{error_ext::ResultExt, kw, parse::rollback_err};
 
 #[derive(Clone)]
 pub enum Attr {
     Kv(KvAttr),
     Directive(Directive),
     Spread(SpreadAttr),
+    ClassList(ClassList),
+    StyleList(StyleList),
 }
 
 impl Parse for Attr {
@@ -24,10 +32,16 @@ impl Parse for Attr {
         // ident then colon must be directive
         // just ident must be regular kv attribute
         // otherwise, try kv or spread
+        // `class` or `style` then `=(` is a list of classes or styles
+        let is_list = input.peek2(Token![=]) && input.peek3(syn::token::Paren);
         if input.peek(syn::Ident::peek_any) && input.peek2(Token![:]) {
             // cannot be anything else, abort if fails
             let dir = Directive::parse(input).unwrap_or_abort();
             Ok(Self::Directive(dir))
+        } else if is_list && input.peek(kw::class) {
+            Ok(Self::ClassList(ClassList::parse(input).unwrap_or_abort()))
+        } else if is_list && input.peek(kw::style) {
+            Ok(Self::StyleList(StyleList::parse(input).unwrap_or_abort()))
         } else if input.peek(syn::Ident) {
             // definitely a k-v attribute
             let kv = KvAttr::parse(input)?;
@@ -43,6 +57,21 @@ impl Parse for Attr {
     }
 }
 
+impl Attr {
+    /// Returns a [`Span`] to use for errors about the whole attribute.
+    ///
+    /// Directives use the span of the directive name.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Kv(kv) => kv.span(),
+            Self::Directive(dir) => dir.dir.span(),
+            Self::Spread(spread) => spread.span(),
+            Self::ClassList(list) => list.span(),
+            Self::StyleList(list) => list.span(),
+        }
+    }
+}
+
 /// A space-separated series of attributes.
 #[derive(Clone)]
 pub struct Attrs(Vec<Attr>);
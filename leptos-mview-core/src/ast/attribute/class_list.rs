@@ -0,0 +1,90 @@
+use proc_macro2::Span;
+use proc_macro_error2::emit_error;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Token,
+};
+
+use crate::{ast::Value, kw, parse::extract_parenthesized, span};
+
+/// A `class` attribute with a list of classes, like
+/// `class=("btn", "active" => [is_active()], {extra})`.
+///
+/// # Examples
+/// ```ignore
+/// button class=("btn btn-lg", "active" => [is_active()], {extra_classes});
+///        ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Clone)]
+pub struct ClassList {
+    key: kw::class,
+    parens: syn::token::Paren,
+    entries: Vec<ClassEntry>,
+}
+
+/// One item in a [`ClassList`].
+#[derive(Clone)]
+pub enum ClassEntry {
+    /// A string literal of one or more space-separated classes, which are
+    /// always added.
+    Static(syn::LitStr),
+    /// `"name" => [condition]`, added when the condition is true.
+    Conditional { name: syn::LitStr, condition: Value },
+    /// A block or bracketed value of one or more space-separated classes.
+    Dynamic(Value),
+}
+
+impl Parse for ClassList {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse::<kw::class>()?;
+        <Token![=]>::parse(input)?;
+        let (parens, inner) = extract_parenthesized(input)?;
+        let entries = Punctuated::<ClassEntry, Token![,]>::parse_terminated(&inner)?;
+
+        Ok(Self {
+            key,
+            parens,
+            entries: entries.into_iter().collect(),
+        })
+    }
+}
+
+impl Parse for ClassEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let value = Value::parse(input)?;
+        let arrow = input.parse::<Option<Token![=>]>>()?;
+
+        match (value, arrow) {
+            (Value::Lit(syn::Lit::Str(name)), Some(arrow)) => {
+                let condition = Value::parse_or_emit_err(input, arrow.spans[1]);
+                if name.value().split_whitespace().count() != 1 {
+                    emit_error!(
+                        name.span(),
+                        "conditional classes must be a single class name"
+                    );
+                }
+                Ok(Self::Conditional { name, condition })
+            }
+            (Value::Lit(syn::Lit::Str(lit)), None) => Ok(Self::Static(lit)),
+            (Value::Lit(lit), _) => Err(syn::Error::new(
+                lit.span(),
+                "expected a string literal, block or bracketed value",
+            )),
+            (_, Some(arrow)) => Err(syn::Error::new(
+                arrow.spans[0],
+                "conditional class names must be string literals",
+            )),
+            (value, None) => Ok(Self::Dynamic(value)),
+        }
+    }
+}
+
+impl ClassList {
+    /// Returns the `class` key.
+    pub const fn key(&self) -> &kw::class { &self.key }
+
+    pub fn entries(&self) -> &[ClassEntry] { &self.entries }
+
+    pub fn span(&self) -> Span { span::join(self.key.span, self.parens.span.join()) }
+}
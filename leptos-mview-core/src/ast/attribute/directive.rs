@@ -1,3 +1,5 @@
+use proc_macro2::TokenStream;
+use proc_macro_error2::emit_error;
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseStream},
@@ -6,7 +8,7 @@ use syn::{
 
 use crate::{
     ast::{BracedKebabIdent, KebabIdentOrStr, Value},
-    parse::rollback_err,
+    parse::{self, rollback_err},
 };
 
 /// A special attribute like `on:click={...}`.
@@ -23,17 +25,34 @@ use crate::{
 /// button class:{primary} style:color="grey";
 /// ```
 ///
-/// If an extra `:modifier` is added, there will also be a modifier.
+/// Any number of extra `:modifier`s can be added after the key.
 /// ```ignore
 /// button on:click:undelegated={on_click};
+/// form on:submit:prevent:stop={on_submit};
 /// ```
 /// `on:{click}:undelegated` also works for the shorthand.
+///
+/// Event handlers in brackets ignore the event, and closure arguments can be
+/// added before the brackets to name it:
+/// ```ignore
+/// button on:click=[set_open(true)];
+/// input on:input=|ev|[set_name(event_target_value(&ev))];
+/// ```
+///
+/// Async handlers are spawned with `spawn_local` for each event:
+/// ```ignore
+/// button on:click=async { save().await };
+/// button on:click=async |ev| { log(ev).await };
+/// ```
+/// This is the same as adding an `:async` modifier to a bracketed handler,
+/// like `on:click:async=|ev|[log(ev).await]`.
 #[derive(Clone)]
 pub struct Directive {
     pub(crate) dir: syn::Ident,
     pub(crate) key: KebabIdentOrStr,
-    pub(crate) modifier: Option<syn::Ident>, // on:event:undelegated
+    pub(crate) modifiers: Vec<syn::Ident>, // on:event:prevent:stop
     pub(crate) value: Option<Value>,
+    pub(crate) handler_args: Option<TokenStream>, // on:click=|ev|[...]
 }
 
 impl Parse for Directive {
@@ -41,36 +60,119 @@ impl Parse for Directive {
         let name = syn::Ident::parse_any(input)?;
         <Token![:]>::parse(input)?;
 
-        let try_parse_modifier = |input| {
-            rollback_err(input, <Token![:]>::parse)
-                .is_some()
-                .then(|| syn::Ident::parse_any(input))
-                .transpose()
+        let parse_modifiers = |input| {
+            let mut modifiers = Vec::new();
+            while rollback_err(input, <Token![:]>::parse).is_some() {
+                if input.peek(syn::LitInt) {
+                    modifiers.push(parse_digit_modifier(input)?);
+                } else {
+                    modifiers.push(syn::Ident::parse_any(input)?);
+                }
+            }
+            syn::Result::Ok(modifiers)
         };
 
         let key: KebabIdentOrStr;
         let value: Option<Value>;
-        let modifier: Option<syn::Ident>;
+        let mut modifiers: Vec<syn::Ident>;
+        let mut handler_args = None;
 
         if input.peek(syn::token::Brace) {
             // on:{click}:undelegated
             let ident = BracedKebabIdent::parse(input)?;
             key = KebabIdentOrStr::KebabIdent(ident.ident().clone());
             value = Some(ident.into_block_value());
-            modifier = try_parse_modifier(input)?;
+            modifiers = parse_modifiers(input)?;
         } else {
-            // on:click:undelegated={on_click}
+            // on:click:prevent:stop={on_click}
             key = KebabIdentOrStr::parse(input)?;
-            modifier = try_parse_modifier(input)?;
-            value = rollback_err(input, <Token![=]>::parse)
-                .map(|eq| Value::parse_or_emit_err(input, eq.span));
+            modifiers = parse_modifiers(input)?;
+            value = match rollback_err(input, <Token![=]>::parse) {
+                Some(_) if input.peek(Token![async]) => {
+                    let (async_token, args, handler) = parse_async_handler(&name, input)?;
+                    modifiers.push(syn::Ident::new("async", async_token.span));
+                    handler_args = args;
+                    Some(handler)
+                }
+                Some(_) if input.peek(Token![|]) => {
+                    let (args, handler) = parse_handler_closure(&name, input)?;
+                    handler_args = Some(args);
+                    Some(handler)
+                }
+                Some(eq) => Some(Value::parse_or_emit_err(input, eq.span)),
+                None => None,
+            };
         };
 
         Ok(Self {
             dir: name,
             key,
-            modifier,
+            modifiers,
             value,
+            handler_args,
         })
     }
 }
+
+/// Parses a digit key filter like the `1` in `on:keydown:1`, which is not an
+/// identifier. It is converted into the modifier `digit1`.
+fn parse_digit_modifier(input: ParseStream) -> syn::Result<syn::Ident> {
+    let lit = syn::LitInt::parse(input)?;
+    let digit = lit.to_string();
+    if digit.len() == 1 {
+        Ok(syn::Ident::new(&format!("digit{digit}"), lit.span()))
+    } else {
+        Err(syn::Error::new(
+            lit.span(),
+            "expected a modifier or a single digit key",
+        ))
+    }
+}
+
+/// Parses an event handler like `|ev|[...]`, returning the closure arguments
+/// without the pipes and the bracketed value.
+fn parse_handler_closure(
+    dir: &syn::Ident,
+    input: ParseStream,
+) -> syn::Result<(TokenStream, Value)> {
+    if dir != "on" {
+        emit_error!(
+            input.span(), "closure arguments are only supported on `on:` directives";
+            help = "use a block `{...}` for the full closure instead"
+        );
+    }
+    let args = parse::closure_pat(input)?;
+    let (brackets, tokens) = parse::bracketed_tokens(input)?;
+    let value = Value::Bracket {
+        tokens,
+        brackets,
+        prefixes: None,
+    };
+    Ok((args, value))
+}
+
+/// Parses an async event handler like `async |ev| {...}` or `async move {...}`,
+/// returning the `async` token, the optional closure arguments and the body
+/// as a bracketed value.
+fn parse_async_handler(
+    dir: &syn::Ident,
+    input: ParseStream,
+) -> syn::Result<(Token![async], Option<TokenStream>, Value)> {
+    let async_token = <Token![async]>::parse(input)?;
+    if dir != "on" {
+        emit_error!(
+            async_token.span, "async handlers are only supported on `on:` directives";
+            help = "use a block `{...}` instead"
+        );
+    }
+    // the future is always moved into `spawn_local`
+    _ = rollback_err(input, <Token![move]>::parse);
+    let args = if input.peek(Token![|]) { Some(parse::closure_pat(input)?) } else { None };
+    let (braces, tokens) = parse::braced_tokens(input)?;
+    let value = Value::Bracket {
+        tokens,
+        brackets: syn::token::Bracket { span: braces.span },
+        prefixes: None,
+    };
+    Ok((async_token, args, value))
+}
@@ -0,0 +1,74 @@
+use proc_macro2::Span;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Token,
+};
+
+use crate::{
+    ast::{KebabIdent, Value},
+    kw,
+    parse::extract_parenthesized,
+    span,
+};
+
+/// A `style` attribute with a list of style properties, like
+/// `style=(color: [c()], margin-top: "4px")`.
+///
+/// # Examples
+/// ```ignore
+/// div style=(color: [c()], margin-top: "4px", --accent: {accent});
+///     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Clone)]
+pub struct StyleList {
+    key: kw::style,
+    parens: syn::token::Paren,
+    entries: Vec<StyleEntry>,
+}
+
+/// One `property: value` in a [`StyleList`].
+#[derive(Clone)]
+pub struct StyleEntry {
+    property: KebabIdent,
+    value: Value,
+}
+
+impl Parse for StyleList {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse::<kw::style>()?;
+        <Token![=]>::parse(input)?;
+        let (parens, inner) = extract_parenthesized(input)?;
+        let entries = Punctuated::<StyleEntry, Token![,]>::parse_terminated(&inner)?;
+
+        Ok(Self {
+            key,
+            parens,
+            entries: entries.into_iter().collect(),
+        })
+    }
+}
+
+impl Parse for StyleEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let property = KebabIdent::parse(input)?;
+        let colon = <Token![:]>::parse(input)?;
+        let value = Value::parse_or_emit_err(input, colon.span);
+        Ok(Self { property, value })
+    }
+}
+
+impl StyleList {
+    /// Returns the `style` key.
+    pub const fn key(&self) -> &kw::style { &self.key }
+
+    pub fn entries(&self) -> &[StyleEntry] { &self.entries }
+
+    pub fn span(&self) -> Span { span::join(self.key.span, self.parens.span.join()) }
+}
+
+impl StyleEntry {
+    pub const fn property(&self) -> &KebabIdent { &self.property }
+
+    pub const fn value(&self) -> &Value { &self.value }
+}
@@ -7,7 +7,7 @@ use syn::{
     parse_quote, Token,
 };
 
-use super::{Doctype, Element};
+use super::{Await, Comment, Doctype, Element, For, Fragment, GlobalListener, If, Let, Match, Raw};
 use crate::{
     ast::Value,
     error_ext::SynErrorExt,
@@ -22,6 +22,17 @@ pub enum NodeChild {
     Value(Value),
     Element(Element),
     Doctype(Doctype),
+    Comment(Comment),
+    Fragment(Fragment),
+    If(If),
+    For(For),
+    Match(Match),
+    Await(Await),
+    Raw(Raw),
+    GlobalListener(GlobalListener),
+    /// Not a view by itself: the siblings after it are nested in a block with
+    /// the `let` statement when expanding.
+    Let(Let),
 }
 
 impl ToTokens for NodeChild {
@@ -30,6 +41,15 @@ impl ToTokens for NodeChild {
             Self::Value(v) => v.into_token_stream(),
             Self::Element(e) => e.into_token_stream(),
             Self::Doctype(d) => d.into_token_stream(),
+            Self::Comment(c) => c.into_token_stream(),
+            Self::Fragment(f) => f.into_token_stream(),
+            Self::If(i) => i.into_token_stream(),
+            Self::For(f) => f.into_token_stream(),
+            Self::Match(m) => m.into_token_stream(),
+            Self::Await(a) => a.into_token_stream(),
+            Self::Raw(r) => r.into_token_stream(),
+            Self::GlobalListener(g) => g.into_token_stream(),
+            Self::Let(l) => l.into_token_stream(),
         };
         tokens.extend(quote! {
             #child_tokens
@@ -43,14 +63,110 @@ impl NodeChild {
             Self::Value(v) => v.span(),
             Self::Element(e) => e.tag().span(),
             Self::Doctype(d) => d.span(),
+            Self::Comment(c) => c.span(),
+            Self::Fragment(f) => f.span(),
+            Self::If(i) => i.span(),
+            Self::For(f) => f.span(),
+            Self::Match(m) => m.span(),
+            Self::Await(a) => a.span(),
+            Self::Raw(r) => r.span(),
+            Self::GlobalListener(g) => g.span(),
+            Self::Let(l) => l.span(),
         }
     }
 }
 
+/// Converts a literal to the string that it would be rendered as.
+///
+/// Errors if the literal does not have a string representation (e.g. byte
+/// strings), or if a number does not fit in its type.
+fn lit_to_lit_str(lit: &syn::Lit) -> syn::Result<syn::LitStr> {
+    let string = match lit {
+        syn::Lit::Str(s) => return Ok(s.clone()),
+        syn::Lit::Char(c) => c.value().to_string(),
+        syn::Lit::Bool(b) => b.value.to_string(),
+        syn::Lit::Int(i) => int_lit_to_string(i)?,
+        syn::Lit::Float(f) => float_lit_to_string(f)?,
+        _ => {
+            return Err(syn::Error::new(
+                lit.span(),
+                "only string, number, bool and char literals are allowed in children",
+            ))
+        }
+    };
+    Ok(syn::LitStr::new(&string, lit.span()))
+}
+
+/// Parses an integer literal as the type of its suffix, or `i32` if there is
+/// no suffix, so that values out of range are rejected like rustc does.
+///
+/// Parsing also normalizes hex/octal/binary literals.
+fn int_lit_to_string(int: &syn::LitInt) -> syn::Result<String> {
+    let ty = if int.suffix().is_empty() { "i32" } else { int.suffix() };
+    let parsed = match ty {
+        "i8" => int.base10_parse::<i8>().map(|n| n.to_string()),
+        "i16" => int.base10_parse::<i16>().map(|n| n.to_string()),
+        "i32" => int.base10_parse::<i32>().map(|n| n.to_string()),
+        "i64" => int.base10_parse::<i64>().map(|n| n.to_string()),
+        "i128" => int.base10_parse::<i128>().map(|n| n.to_string()),
+        "isize" => int.base10_parse::<isize>().map(|n| n.to_string()),
+        "u8" => int.base10_parse::<u8>().map(|n| n.to_string()),
+        "u16" => int.base10_parse::<u16>().map(|n| n.to_string()),
+        "u32" => int.base10_parse::<u32>().map(|n| n.to_string()),
+        "u64" => int.base10_parse::<u64>().map(|n| n.to_string()),
+        "u128" => int.base10_parse::<u128>().map(|n| n.to_string()),
+        "usize" => int.base10_parse::<usize>().map(|n| n.to_string()),
+        // floats can be written like `1f32`
+        "f32" | "f64" => return float_lit_to_string(&syn::LitFloat::from(int.token())),
+        _ => {
+            return Err(syn::Error::new(
+                int.span(),
+                format!("invalid suffix `{ty}` for number literal"),
+            ))
+        }
+    };
+    parsed.map_err(|_| {
+        syn::Error::new(
+            int.span(),
+            format!("integer literal is out of range for `{ty}`"),
+        )
+    })
+}
+
+/// Parses a float literal to format it the same as `Display` does at runtime,
+/// like `1e3` to `1000`.
+fn float_lit_to_string(float: &syn::LitFloat) -> syn::Result<String> {
+    let ty = if float.suffix().is_empty() { "f64" } else { float.suffix() };
+    let parsed = match ty {
+        "f32" => float
+            .base10_parse::<f32>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .map(|n| n.to_string()),
+        "f64" => float
+            .base10_parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .map(|n| n.to_string()),
+        _ => {
+            return Err(syn::Error::new(
+                float.span(),
+                format!("invalid suffix `{ty}` for float literal"),
+            ))
+        }
+    };
+    parsed.ok_or_else(|| {
+        syn::Error::new(
+            float.span(),
+            format!("float literal is out of range for `{ty}`"),
+        )
+    })
+}
+
 /// Possible child items inside a component.
 ///
-/// If the child is a `Value::Lit`, this lit must be a string. Parsing will
-/// abort if the lit is not a string.
+/// If the child is a `Value::Lit`, it is converted to a string literal, see
+/// [`lit_to_lit_str`].
 ///
 /// Children can either be a [`NodeChild`] (i.e. an actual element), or a slot.
 /// Slots are distinguished by prefixing the child with `slot:`.
@@ -62,14 +178,15 @@ pub enum Child {
 impl Parse for Child {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         if let Some(value) = rollback_err(input, Value::parse) {
-            // only allow literals if they are a string.
+            // other literals are converted to strings.
             if let Value::Lit(ref lit) = value {
-                if let syn::Lit::Str(_) = lit {
-                    Ok(Self::Node(NodeChild::Value(value)))
-                } else {
-                    emit_error!(lit.span(), "only string literals are allowed in children");
-                    Ok(Self::Node(NodeChild::Value(Value::Lit(parse_quote!("")))))
-                }
+                let lit_str = lit_to_lit_str(lit).unwrap_or_else(|e| {
+                    e.emit_as_error();
+                    parse_quote!("")
+                });
+                Ok(Self::Node(NodeChild::Value(Value::Lit(syn::Lit::Str(
+                    lit_str,
+                )))))
             } else {
                 Ok(Self::Node(NodeChild::Value(value)))
             }
@@ -79,9 +196,32 @@ impl Parse for Child {
             <Token![:]>::parse(input).unwrap();
             let elem = Element::parse(input)?;
             Ok(Self::Slot(slot, elem))
+        } else if input.peek(Token![@]) && input.peek2(Token![if]) {
+            Ok(Self::Node(NodeChild::If(If::parse(input)?)))
+        } else if input.peek(Token![@]) && input.peek2(Token![for]) {
+            Ok(Self::Node(NodeChild::For(For::parse(input)?)))
+        } else if input.peek(Token![@]) && input.peek2(Token![match]) {
+            Ok(Self::Node(NodeChild::Match(Match::parse(input)?)))
+        } else if input.peek(Token![@]) && input.peek2(Token![await]) {
+            Ok(Self::Node(NodeChild::Await(Await::parse(input)?)))
+        } else if input.peek(Token![@]) && input.peek2(kw::raw) {
+            Ok(Self::Node(NodeChild::Raw(Raw::parse(input)?)))
+        } else if input.peek(Token![@]) && (input.peek2(kw::window) || input.peek2(kw::document)) {
+            Ok(Self::Node(NodeChild::GlobalListener(
+                GlobalListener::parse(input)?,
+            )))
+        } else if input.peek(Token![@]) && input.peek2(Token![let]) {
+            Ok(Self::Node(NodeChild::Let(Let::parse(input)?)))
+        } else if input.peek(Token![@]) && (input.peek2(kw::tag) || input.peek2(kw::component)) {
+            let elem = Element::parse(input)?;
+            Ok(Self::Node(NodeChild::Element(elem)))
+        } else if input.peek(Token![_]) {
+            Ok(Self::Node(NodeChild::Fragment(Fragment::parse(input)?)))
         } else if input.peek(syn::Ident::peek_any) {
             let elem = Element::parse(input)?;
             Ok(Self::Node(NodeChild::Element(elem)))
+        } else if input.peek(Token![!]) && input.peek2(Token![-]) {
+            Ok(Self::Node(NodeChild::Comment(Comment::parse(input)?)))
         } else if let Some(doctype) = rollback_err(input, Doctype::parse) {
             Ok(Self::Node(NodeChild::Doctype(doctype)))
         } else {
@@ -134,6 +274,18 @@ impl Parse for Children {
 }
 
 impl Children {
+    /// Parses children wrapped in either braces `{ ... }` or parentheses
+    /// `( ... )`.
+    pub fn parse_delimited(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::token::Brace) {
+            Ok(parse::braced::<Self>(input)?.1)
+        } else if input.peek(syn::token::Paren) {
+            Ok(parse::parenthesized::<Self>(input)?.1)
+        } else {
+            Err(input.error("expected children block in braces or parentheses"))
+        }
+    }
+
     pub fn into_vec(self) -> Vec<Child> { self.0 }
 
     /// Returns an iterator of all children that are not slots.
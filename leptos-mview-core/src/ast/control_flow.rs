@@ -0,0 +1,652 @@
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error2::emit_error;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    Token,
+};
+
+use super::{attribute::directive::Directive, Children, Value};
+use crate::{
+    expand::{
+        await_to_tokens, for_to_tokens, global_listener_to_tokens, if_to_tokens, match_to_tokens,
+        raw_to_tokens,
+    },
+    kw,
+    parse::{self, rollback_err},
+};
+
+/// A reactive conditional, like
+/// `@if [cond] (...) @else if let Some(x) = [opt] (...) @else (...)`.
+///
+/// Conditions must be wrapped in brackets or braces, the same as other
+/// non-literal values. As the whole chain is expanded into a reactive closure,
+/// both `[cond]` and `{cond}` are placed directly into the `if`.
+///
+/// The `@else` branch is optional: if it is missing, the chain renders nothing
+/// when none of the conditions match.
+pub struct If {
+    at: Token![@],
+    branches: Vec<IfBranch>,
+    else_branch: Option<(Token![else], Children)>,
+}
+
+/// A single `if [cond] (...)` or `if let pat = [expr] (...)` branch.
+pub struct IfBranch {
+    pub(crate) if_token: Token![if],
+    pub(crate) condition: IfCondition,
+    pub(crate) children: Children,
+}
+
+/// The condition of an [`IfBranch`].
+pub enum IfCondition {
+    /// `[cond]` or `{cond}`.
+    Expr(TokenStream),
+    /// `let pat = [expr]`.
+    ///
+    /// The pattern is stored as a raw [`TokenStream`], and includes everything
+    /// between the `let` and the `=`.
+    Let {
+        let_token: Token![let],
+        pat: TokenStream,
+        expr: TokenStream,
+    },
+}
+
+impl Parse for If {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let at = <Token![@]>::parse(input)?;
+        let mut branches = vec![IfBranch::parse(input)?];
+        let mut else_branch = None;
+
+        while input.peek(Token![@]) && input.peek2(Token![else]) {
+            <Token![@]>::parse(input)?;
+            let else_token = <Token![else]>::parse(input)?;
+            if input.peek(Token![if]) {
+                branches.push(IfBranch::parse(input)?);
+            } else {
+                else_branch = Some((else_token, Children::parse_delimited(input)?));
+                break;
+            }
+        }
+
+        Ok(Self {
+            at,
+            branches,
+            else_branch,
+        })
+    }
+}
+
+impl Parse for IfBranch {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let if_token = <Token![if]>::parse(input)?;
+        let condition = if let Some(let_token) = rollback_err(input, <Token![let]>::parse) {
+            let pat = parse_until_eq(input)?;
+            let expr = parse_delimited_expr(input)?;
+            IfCondition::Let {
+                let_token,
+                pat,
+                expr,
+            }
+        } else {
+            IfCondition::Expr(parse_delimited_expr(input)?)
+        };
+        let children = Children::parse_delimited(input)?;
+
+        Ok(Self {
+            if_token,
+            condition,
+            children,
+        })
+    }
+}
+
+impl ToTokens for If {
+    fn to_tokens(&self, tokens: &mut TokenStream) { tokens.extend(if_to_tokens(self)); }
+}
+
+impl If {
+    /// Returns the span of the leading `@`.
+    pub fn span(&self) -> Span { self.at.span }
+
+    /// Returns all the `if` and `else if` branches, in order.
+    pub fn branches(&self) -> &[IfBranch] { &self.branches }
+
+    /// Returns the `else` branch, if there is one.
+    pub const fn else_branch(&self) -> Option<&(Token![else], Children)> {
+        self.else_branch.as_ref()
+    }
+}
+
+/// An iteration over a list, like `@for item in [items()] key={item.id} (...)`.
+///
+/// If a `key` is provided, this is expanded to a keyed
+/// [`For`](https://docs.rs/leptos/latest/leptos/control_flow/fn.For.html)
+/// component. The key expression has access to the pattern bindings (as
+/// references). Otherwise, the iterable is mapped directly to views and
+/// collected.
+///
+/// The iterable follows the same rules as other values: brackets `[items()]`
+/// are reactive, while braces `{items}` are not. Note that `For` requires a
+/// closure (or signal) to iterate over, so braces should contain one if a key
+/// is provided.
+///
+/// Patterns like `(i, item)` are supported, everything between the `for` and
+/// `in` is taken as the pattern.
+pub struct For {
+    at: Token![@],
+    pat: TokenStream,
+    iterable: Value,
+    key: Option<(kw::key, TokenStream)>,
+    children: Children,
+}
+
+impl Parse for For {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let at = <Token![@]>::parse(input)?;
+        <Token![for]>::parse(input)?;
+        let pat = parse_until_in(input)?;
+        let iterable = Value::parse(input)?;
+
+        let key = if input.peek(kw::key) && input.peek2(Token![=]) {
+            let key = kw::key::parse(input)?;
+            <Token![=]>::parse(input)?;
+            Some((key, parse_delimited_expr(input)?))
+        } else {
+            None
+        };
+        let children = Children::parse_delimited(input)?;
+
+        Ok(Self {
+            at,
+            pat,
+            iterable,
+            key,
+            children,
+        })
+    }
+}
+
+impl ToTokens for For {
+    fn to_tokens(&self, tokens: &mut TokenStream) { tokens.extend(for_to_tokens(self)); }
+}
+
+impl For {
+    /// Returns the span of the leading `@`.
+    pub fn span(&self) -> Span { self.at.span }
+
+    /// Returns the pattern that each item is bound to.
+    pub const fn pat(&self) -> &TokenStream { &self.pat }
+
+    /// Returns the value being iterated over.
+    pub const fn iterable(&self) -> &Value { &self.iterable }
+
+    pub const fn key(&self) -> Option<&(kw::key, TokenStream)> { self.key.as_ref() }
+
+    pub const fn children(&self) -> &Children { &self.children }
+}
+
+/// A reactive `match`, like
+/// `@match [state()] { State::Loading => ("loading"), State::Ready(x) => ({x})
+/// }`.
+///
+/// The scrutinee must be wrapped in brackets or braces, and each arm's children
+/// in parentheses or braces. Arms may be separated by optional commas.
+pub struct Match {
+    at: Token![@],
+    keyword: Token![match],
+    expr: TokenStream,
+    arms: Vec<MatchArm>,
+}
+
+/// A single `pat => (...)` arm of a [`Match`].
+///
+/// The pattern is stored as a raw [`TokenStream`], and includes everything
+/// before the `=>`, including any `if` guard.
+pub struct MatchArm {
+    pub(crate) pat: TokenStream,
+    pub(crate) fat_arrow: Token![=>],
+    pub(crate) children: Children,
+}
+
+/// All the arms inside the braces of a [`Match`].
+struct MatchArms(Vec<MatchArm>);
+
+impl Parse for Match {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let at = <Token![@]>::parse(input)?;
+        let keyword = <Token![match]>::parse(input)?;
+        let expr = parse_delimited_expr(input)?;
+        let (_, MatchArms(arms)) = parse::braced::<MatchArms>(input)
+            .map_err(|e| syn::Error::new(e.span(), "expected match arms in braces"))?;
+
+        Ok(Self {
+            at,
+            keyword,
+            expr,
+            arms,
+        })
+    }
+}
+
+impl Parse for MatchArm {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut pat = TokenStream::new();
+        let fat_arrow = loop {
+            if let Some(fat_arrow) = rollback_err(input, <Token![=>]>::parse) {
+                break fat_arrow;
+            } else if let Some(tt) = rollback_err(input, proc_macro2::TokenTree::parse) {
+                pat.extend([tt]);
+            } else {
+                return Err(input.error("expected `=>` after pattern"));
+            }
+        };
+        let children = Children::parse_delimited(input)?;
+
+        Ok(Self {
+            pat,
+            fat_arrow,
+            children,
+        })
+    }
+}
+
+impl Parse for MatchArms {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut arms = Vec::new();
+        while !input.is_empty() {
+            arms.push(MatchArm::parse(input)?);
+            // commas between arms are optional
+            rollback_err(input, <Token![,]>::parse);
+        }
+        Ok(Self(arms))
+    }
+}
+
+impl ToTokens for Match {
+    fn to_tokens(&self, tokens: &mut TokenStream) { tokens.extend(match_to_tokens(self)); }
+}
+
+impl Match {
+    /// Returns the span of the leading `@`.
+    pub fn span(&self) -> Span { self.at.span }
+
+    pub const fn keyword(&self) -> &Token![match] { &self.keyword }
+
+    /// Returns the expression being matched on, without its delimiters.
+    pub const fn expr(&self) -> &TokenStream { &self.expr }
+
+    pub fn arms(&self) -> &[MatchArm] { &self.arms }
+}
+
+/// A template-local binding, like `@let doubled = count() * 2;`.
+///
+/// The binding is visible to all later siblings (and their descendants) in the
+/// same children block. The expression is taken as-is up until the `;`, and is
+/// not wrapped in brackets or braces.
+pub struct Let {
+    at: Token![@],
+    keyword: Token![let],
+    pat: TokenStream,
+    expr: TokenStream,
+    semi: Token![;],
+}
+
+impl Parse for Let {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let at = <Token![@]>::parse(input)?;
+        let keyword = <Token![let]>::parse(input)?;
+        let pat = parse_until_eq(input)?;
+
+        let mut expr = TokenStream::new();
+        let semi = loop {
+            if let Some(semi) = rollback_err(input, <Token![;]>::parse) {
+                break semi;
+            } else if let Some(tt) = rollback_err(input, proc_macro2::TokenTree::parse) {
+                expr.extend([tt]);
+            } else {
+                return Err(input.error("expected `;` after `@let` expression"));
+            }
+        };
+        if expr.is_empty() {
+            return Err(syn::Error::new(semi.span, "expected an expression"));
+        }
+
+        Ok(Self {
+            at,
+            keyword,
+            pat,
+            expr,
+            semi,
+        })
+    }
+}
+
+/// Expands to the `let` statement, including the trailing semicolon.
+impl ToTokens for Let {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            keyword,
+            pat,
+            expr,
+            semi,
+            ..
+        } = self;
+        tokens.extend(quote! { #keyword #pat = #expr #semi });
+    }
+}
+
+impl Let {
+    /// Returns the span of the leading `@`.
+    pub fn span(&self) -> Span { self.at.span }
+}
+
+/// Waits for a future before rendering, like
+/// `@await {fetch()} fallback=("loading...") |data| (p({data}))`.
+///
+/// The future must be wrapped in brackets or braces, and is only run once. The
+/// resolved value is bound to the pattern in the closure pipes (if there is
+/// one), which is an owned clone of the value.
+///
+/// The `fallback` is optional, and is shown while the future is pending.
+pub struct Await {
+    at: Token![@],
+    keyword: Token![await],
+    future: TokenStream,
+    fallback: Option<(kw::fallback, Children)>,
+    pat: Option<TokenStream>,
+    children: Children,
+}
+
+impl Parse for Await {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let at = <Token![@]>::parse(input)?;
+        let keyword = <Token![await]>::parse(input)?;
+        let future = parse_delimited_expr(input)?;
+
+        let fallback = if input.peek(kw::fallback) && input.peek2(Token![=]) {
+            let fallback = kw::fallback::parse(input)?;
+            <Token![=]>::parse(input)?;
+            Some((fallback, Children::parse_delimited(input)?))
+        } else {
+            None
+        };
+
+        let pat = if input.peek(Token![|]) { Some(parse::closure_pat(input)?) } else { None };
+        let children = Children::parse_delimited(input)?;
+
+        Ok(Self {
+            at,
+            keyword,
+            future,
+            fallback,
+            pat,
+            children,
+        })
+    }
+}
+
+impl ToTokens for Await {
+    fn to_tokens(&self, tokens: &mut TokenStream) { tokens.extend(await_to_tokens(self)); }
+}
+
+impl Await {
+    /// Returns the span of the leading `@`.
+    pub fn span(&self) -> Span { self.at.span }
+
+    pub const fn keyword(&self) -> &Token![await] { &self.keyword }
+
+    /// Returns the future, without its delimiters.
+    pub const fn future(&self) -> &TokenStream { &self.future }
+
+    pub const fn fallback(&self) -> Option<&(kw::fallback, Children)> { self.fallback.as_ref() }
+
+    /// Returns the pattern inside the closure pipes, without the pipes.
+    pub const fn pat(&self) -> Option<&TokenStream> { self.pat.as_ref() }
+
+    pub const fn children(&self) -> &Children { &self.children }
+}
+
+/// Unescaped HTML, like `@raw "<b>hi</b>"` or `@raw(section) {markdown_html}`.
+///
+/// The value can be a string literal, block or bracket (for reactive HTML).
+/// An optional tag in parentheses names the wrapper element that blocks and
+/// brackets are rendered into, which is a `div` by default.
+pub struct Raw {
+    at: Token![@],
+    keyword: kw::raw,
+    tag: Option<syn::Ident>,
+    value: Value,
+}
+
+impl Parse for Raw {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let at = <Token![@]>::parse(input)?;
+        let keyword = kw::raw::parse(input)?;
+        let tag = if input.peek(syn::token::Paren) {
+            let (_, tag) = parse::parenthesized::<syn::Ident>(input).map_err(|e| {
+                syn::Error::new(e.span(), "expected a wrapper element name in parentheses")
+            })?;
+            Some(tag)
+        } else {
+            None
+        };
+        let value = Value::parse(input)?;
+        Ok(Self {
+            at,
+            keyword,
+            tag,
+            value,
+        })
+    }
+}
+
+impl ToTokens for Raw {
+    fn to_tokens(&self, tokens: &mut TokenStream) { tokens.extend(raw_to_tokens(self)); }
+}
+
+impl Raw {
+    /// Returns the span of the leading `@`.
+    pub fn span(&self) -> Span { self.at.span }
+
+    pub const fn keyword(&self) -> &kw::raw { &self.keyword }
+
+    /// Returns the wrapper element in `@raw(tag)`, if there is one.
+    pub const fn tag(&self) -> Option<&syn::Ident> { self.tag.as_ref() }
+
+    pub const fn value(&self) -> &Value { &self.value }
+
+    /// Whether this is a string literal without a wrapper, which is expanded
+    /// to an `InertElement` instead of an element with `inner_html`.
+    pub const fn is_inert(&self) -> bool {
+        self.tag.is_none() && matches!(self.value, Value::Lit(syn::Lit::Str(_)))
+    }
+}
+
+/// Event listeners on the window or document, like
+/// `@window on:resize={handle_resize};`.
+///
+/// Any number of `on:` directives can be added before the `;`.
+pub struct GlobalListener {
+    at: Token![@],
+    target: syn::Ident,
+    listeners: Vec<Directive>,
+}
+
+impl Parse for GlobalListener {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let at = <Token![@]>::parse(input)?;
+        let target = if input.peek(kw::window) || input.peek(kw::document) {
+            syn::Ident::parse(input)?
+        } else {
+            return Err(input.error("expected `window` or `document`"));
+        };
+
+        let mut listeners = Vec::new();
+        while !input.is_empty() && !input.peek(Token![;]) {
+            let listener = Directive::parse(input)?;
+            if listener.dir != "on" {
+                emit_error!(
+                    listener.dir.span(),
+                    "only `on:` directives are supported on `@{}`",
+                    target
+                );
+            }
+            listeners.push(listener);
+        }
+        let semi = <Token![;]>::parse(input)?;
+        if listeners.is_empty() {
+            emit_error!(semi.span, "expected an `on:` directive before the `;`");
+        }
+
+        Ok(Self {
+            at,
+            target,
+            listeners,
+        })
+    }
+}
+
+impl ToTokens for GlobalListener {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(global_listener_to_tokens(self));
+    }
+}
+
+impl GlobalListener {
+    /// Returns the span of the leading `@`.
+    pub fn span(&self) -> Span { self.at.span }
+
+    /// Returns either `window` or `document`.
+    pub const fn target(&self) -> &syn::Ident { &self.target }
+
+    pub fn listeners(&self) -> &[Directive] { &self.listeners }
+}
+
+/// Parses an expression wrapped in brackets `[...]` or braces `{...}`,
+/// returning the tokens without the delimiters.
+pub fn parse_delimited_expr(input: ParseStream) -> syn::Result<TokenStream> {
+    if input.peek(syn::token::Bracket) {
+        Ok(parse::bracketed_tokens(input)?.1)
+    } else if input.peek(syn::token::Brace) {
+        Ok(parse::braced_tokens(input)?.1)
+    } else {
+        Err(input.error("expected an expression wrapped in brackets `[...]` or braces `{...}`"))
+    }
+}
+
+/// Parses a pattern up until (and including) the next `=`.
+///
+/// Patterns cannot contain a bare `=` outside of a nested group, except for as
+/// part of an inclusive range `..=`, which is joined to the previous `.`.
+fn parse_until_eq(input: ParseStream) -> syn::Result<TokenStream> {
+    input.step(|cursor| {
+        let mut tokens = TokenStream::new();
+        let mut rest = *cursor;
+        let mut prev_joint = false;
+        while let Some((tt, next)) = rest.token_tree() {
+            if let proc_macro2::TokenTree::Punct(punct) = &tt {
+                if punct.as_char() == '=' && !prev_joint {
+                    return Ok((tokens, next));
+                }
+                prev_joint = punct.spacing() == proc_macro2::Spacing::Joint;
+            } else {
+                prev_joint = false;
+            }
+            tokens.extend([tt]);
+            rest = next;
+        }
+        Err(cursor.error("expected `=` after pattern"))
+    })
+}
+
+/// Parses a pattern up until (and including) the next `in`.
+fn parse_until_in(input: ParseStream) -> syn::Result<TokenStream> {
+    let mut tokens = TokenStream::new();
+    loop {
+        if rollback_err(input, <Token![in]>::parse).is_some() {
+            break Ok(tokens);
+        } else if let Some(tt) = rollback_err(input, proc_macro2::TokenTree::parse) {
+            tokens.extend([tt]);
+        } else {
+            break Err(input.error("expected `in` after pattern"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+
+    use super::{Await, For, If, IfCondition, Let, Match};
+
+    #[test]
+    fn if_chain() {
+        let input = r#"@if [a()] ("a") @else if let Some(0..=3) = {b} ("b") @else ("c")"#;
+        let if_: If = syn::parse_str(input).unwrap();
+        assert_eq!(if_.branches().len(), 2);
+        assert!(matches!(if_.branches()[0].condition, IfCondition::Expr(_)));
+        let IfCondition::Let { pat, .. } = &if_.branches()[1].condition else {
+            panic!("expected `if let` condition")
+        };
+        assert_eq!(pat.to_string().replace(' ', ""), "Some(0..=3)");
+        assert!(if_.else_branch().is_some());
+    }
+
+    #[test]
+    fn no_else() {
+        let if_: If = syn::parse_str("@if [true] { span; }").unwrap();
+        assert_eq!(if_.branches().len(), 1);
+        assert!(if_.else_branch().is_none());
+    }
+
+    #[test]
+    fn for_patterns() {
+        let input = "@for (i, item) in [items()] key={item.id} (li({i}))";
+        let for_: For = syn::parse_str(input).unwrap();
+        assert_eq!(for_.pat().to_string(), "(i , item)");
+        assert!(for_.key().is_some());
+        assert_eq!(for_.children().len(), 1);
+
+        let for_: For = syn::parse_str("@for x in {0..3} { {x} }").unwrap();
+        assert!(for_.key().is_none());
+    }
+
+    #[test]
+    fn match_arms() {
+        let input = r#"@match [state()] {
+            Some(0) | None => ("none"),
+            Some(x) if x > 3 => { "big" }
+            Some(x) => ({x})
+        }"#;
+        let match_: Match = syn::parse_str(input).unwrap();
+        assert_eq!(match_.arms().len(), 3);
+        assert_eq!(match_.arms()[1].pat.to_string(), "Some (x) if x > 3");
+    }
+
+    #[test]
+    fn let_statement() {
+        let let_: Let = syn::parse_str("@let (a, b): (i32, i32) = { (1, 2) };").unwrap();
+        assert_eq!(let_.pat.to_string(), "(a , b) : (i32 , i32)");
+        assert_eq!(
+            let_.to_token_stream().to_string(),
+            "let (a , b) : (i32 , i32) = { (1 , 2) } ;"
+        );
+
+        assert!(syn::parse_str::<Let>("@let a = ;").is_err());
+        assert!(syn::parse_str::<Let>("@let a = 1").is_err());
+    }
+
+    #[test]
+    fn await_parts() {
+        let input = r#"@await {fetch()} fallback=("loading") |(a, b)| ({a} {b})"#;
+        let await_: Await = syn::parse_str(input).unwrap();
+        assert!(await_.fallback().is_some());
+        assert_eq!(await_.pat().unwrap().to_string(), "(a , b)");
+
+        let await_: Await = syn::parse_str("@await {fetch()} { \"done\" }").unwrap();
+        assert!(await_.fallback().is_none());
+        assert!(await_.pat().is_none());
+    }
+}
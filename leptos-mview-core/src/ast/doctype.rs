@@ -108,3 +108,141 @@ impl ToTokens for Doctype {
         });
     }
 }
+
+/// An HTML comment, like `!-- "comment text"`.
+///
+/// Expands to a view type declared in the expansion, which renders
+/// `<!--comment text-->` in SSR and creates a comment node on the client.
+pub struct Comment {
+    bang: Token![!],
+    text: syn::LitStr,
+}
+
+impl Comment {
+    pub fn span(&self) -> Span { span::join(self.bang.span, self.text.span()) }
+}
+
+impl Parse for Comment {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let bang = <Token![!]>::parse(input)?;
+        <Token![-]>::parse(input)?;
+        <Token![-]>::parse(input)?;
+        let text = input.parse::<syn::LitStr>()?;
+        Ok(Self { bang, text })
+    }
+}
+
+impl ToTokens for Comment {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let text = syn::LitStr::new(&escape_comment(&self.text.value()), self.text.span());
+        let comment = quote_spanned!(self.bang.span=> __MviewComment);
+        let view = quote! { ::leptos::tachys::view };
+        let placeholder = quote! { ::leptos::tachys::renderer::types::Placeholder };
+
+        // the text is always a literal, so it never changes when rebuilding.
+        tokens.extend(quote! {
+            {
+                struct __MviewComment;
+
+                impl #view::Render for __MviewComment {
+                    type State = #placeholder;
+
+                    fn build(self) -> Self::State {
+                        ::leptos::tachys::dom::document().create_comment(#text)
+                    }
+
+                    fn rebuild(self, _state: &mut Self::State) {}
+                }
+
+                ::leptos::tachys::no_attrs!(__MviewComment);
+
+                impl #view::RenderHtml for __MviewComment {
+                    type AsyncOutput = Self;
+
+                    const MIN_LENGTH: usize = "<!---->".len();
+
+                    fn html_len(&self) -> usize { Self::MIN_LENGTH + #text.len() }
+
+                    fn dry_resolve(&mut self) {}
+
+                    async fn resolve(self) -> Self::AsyncOutput { self }
+
+                    fn to_html_with_buf(
+                        self,
+                        buf: &mut ::std::string::String,
+                        position: &mut #view::Position,
+                        _escape: bool,
+                        _mark_branches: bool,
+                    ) {
+                        buf.push_str("<!--");
+                        buf.push_str(#text);
+                        buf.push_str("-->");
+                        *position = #view::Position::NextChild;
+                    }
+
+                    fn hydrate<const FROM_SERVER: bool>(
+                        self,
+                        cursor: &::leptos::tachys::hydration::Cursor,
+                        position: &#view::PositionState,
+                    ) -> Self::State {
+                        let curr_position = position.get();
+                        if curr_position == #view::Position::FirstChild {
+                            cursor.child();
+                        } else if curr_position != #view::Position::Current {
+                            cursor.sibling();
+                        }
+                        let comment = <#placeholder as ::leptos::tachys::renderer::CastFrom<_>>
+                            ::cast_from(cursor.current())
+                            .expect("expected a comment node while hydrating");
+                        position.set(#view::Position::NextChild);
+                        comment
+                    }
+                }
+
+                #comment
+            }
+        });
+    }
+}
+
+/// Escapes the text of a comment so that it can't end the comment early.
+///
+/// A space is added between consecutive hyphens, and around the text if it
+/// starts with `>` or `-` or ends with `-`, so that the text never contains
+/// `--` or joins with the surrounding `<!--` and `-->`.
+fn escape_comment(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    if text.starts_with(['>', '-']) {
+        escaped.push(' ');
+    }
+    for c in text.chars() {
+        if c == '-' && escaped.ends_with('-') {
+            escaped.push(' ');
+        }
+        escaped.push(c);
+    }
+    if escaped.ends_with('-') {
+        escaped.push(' ');
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_comment, Comment, Doctype};
+
+    #[test]
+    fn comment_is_not_doctype() {
+        assert!(syn::parse_str::<Comment>(r#"!-- "text""#).is_ok());
+        assert!(syn::parse_str::<Comment>("!DOCTYPE html;").is_err());
+        assert!(syn::parse_str::<Doctype>("!DOCTYPE html;").is_ok());
+    }
+
+    #[test]
+    fn escape_comments() {
+        assert_eq!(escape_comment(" ko if: visible "), " ko if: visible ");
+        assert_eq!(escape_comment("a -- b"), "a - - b");
+        assert_eq!(escape_comment("--->"), " - - ->");
+        assert_eq!(escape_comment(">x-"), " >x- ");
+    }
+}
@@ -0,0 +1,46 @@
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::{
+    parse::{Parse, ParseStream},
+    Token,
+};
+
+use super::{Attrs, Children};
+use crate::expand::fragment_to_tokens;
+
+/// A group of children without a wrapping element, like
+/// `_ clone:name ("a" strong("b"))`.
+///
+/// Only `clone:` directives are supported, which apply to just the children
+/// inside the group.
+pub struct Fragment {
+    underscore: Token![_],
+    attrs: Attrs,
+    children: Children,
+}
+
+impl Parse for Fragment {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let underscore = <Token![_]>::parse(input)?;
+        let attrs = Attrs::parse(input)?;
+        let children = Children::parse_delimited(input)?;
+        Ok(Self {
+            underscore,
+            attrs,
+            children,
+        })
+    }
+}
+
+impl ToTokens for Fragment {
+    fn to_tokens(&self, tokens: &mut TokenStream) { tokens.extend(fragment_to_tokens(self)); }
+}
+
+impl Fragment {
+    /// Returns the span of the leading `_`.
+    pub fn span(&self) -> Span { self.underscore.span }
+
+    pub const fn attrs(&self) -> &Attrs { &self.attrs }
+
+    pub const fn children(&self) -> &Children { &self.children }
+}
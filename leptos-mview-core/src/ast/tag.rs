@@ -1,4 +1,4 @@
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream};
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseStream},
@@ -6,7 +6,7 @@ use syn::{
     Token,
 };
 
-use crate::ast::KebabIdent;
+use crate::{ast::KebabIdent, kw, parse};
 
 #[allow(clippy::doc_markdown)]
 /// The name of the element, like `div`, `path`, `For`, `leptos-island`, etc.
@@ -25,6 +25,13 @@ use crate::ast::KebabIdent;
 /// component. Turbofish syntax (`Component::<T>`) is not used, the generic is
 /// placed directly after (`Component<T>`).
 ///
+/// Tags chosen at runtime are written as `@tag(expr)`, and are treated like
+/// web-components.
+///
+/// Components can also be explicitly invoked with `@component`, followed by
+/// either a path (for lowercase components like `@component my_view`) or a
+/// block (`@component {icon}`) for components stored in variables.
+///
 /// See [`TagKind`] for a discriminant-only version of this enum.
 pub enum Tag {
     Html(syn::Ident),
@@ -33,6 +40,16 @@ pub enum Tag {
     Svg(syn::Ident),
     Math(syn::Ident),
     WebComponent(KebabIdent),
+    /// The expression inside the parentheses of `@tag(...)`.
+    Dynamic {
+        tag: kw::tag,
+        expr: TokenStream,
+    },
+    /// The expression inside the braces of `@component {...}`.
+    ComponentExpr {
+        component: kw::component,
+        expr: TokenStream,
+    },
 }
 
 impl Tag {
@@ -44,23 +61,44 @@ impl Tag {
             Self::Html(ident) | Self::Svg(ident) | Self::Math(ident) => ident.span(),
             Self::WebComponent(ident) => ident.span(),
             Self::Component(path) => path.span(),
+            Self::Dynamic { tag, .. } => tag.span,
+            Self::ComponentExpr { component, .. } => component.span,
         }
     }
 
     /// Returns the [`TagKind`] of this tag.
     pub fn kind(&self) -> TagKind {
         match self {
-            Tag::Html(_) => TagKind::Html,
-            Tag::Component(_) => TagKind::Component,
-            Tag::Svg(_) => TagKind::Svg,
-            Tag::Math(_) => TagKind::Math,
-            Tag::WebComponent(_) => TagKind::WebComponent,
+            Self::Html(_) => TagKind::Html,
+            Self::Component(_) | Self::ComponentExpr { .. } => TagKind::Component,
+            Self::Svg(_) => TagKind::Svg,
+            Self::Math(_) => TagKind::Math,
+            Self::WebComponent(_) => TagKind::WebComponent,
+            Self::Dynamic { .. } => TagKind::Dynamic,
         }
     }
 }
 
 impl Parse for Tag {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![@]) && input.peek2(kw::tag) {
+            <Token![@]>::parse(input)?;
+            let tag = kw::tag::parse(input)?;
+            let (_, expr) = parse::parenthesized::<TokenStream>(input)
+                .map_err(|e| syn::Error::new(e.span(), "expected tag name in parentheses"))?;
+            return Ok(Self::Dynamic { tag, expr });
+        }
+        if input.peek(Token![@]) && input.peek2(kw::component) {
+            <Token![@]>::parse(input)?;
+            let component = kw::component::parse(input)?;
+            if input.peek(syn::token::Brace) {
+                let (_, expr) = parse::braced_tokens(input)?;
+                return Ok(Self::ComponentExpr { component, expr });
+            }
+            // any path is a component, regardless of case
+            return Ok(Self::Component(syn::Path::parse(input)?));
+        }
+
         // peek 1 in case it's a leading ::
         // this will also include any generics
         // also look for generics without a full path
@@ -78,6 +116,12 @@ impl Parse for Tag {
             TagKind::Svg => Self::Svg(ident.to_snake_ident()),
             TagKind::Math => Self::Math(ident.to_snake_ident()),
             TagKind::WebComponent => Self::WebComponent(ident),
+            TagKind::Dynamic => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "dynamic tags must be written as `@tag(...)`",
+                ))
+            }
         })
     }
 }
@@ -90,6 +134,7 @@ pub enum TagKind {
     Svg,
     Math,
     WebComponent,
+    Dynamic,
 }
 
 impl From<&str> for TagKind {
@@ -1,11 +1,14 @@
+use std::{iter::Peekable, str::Chars};
+
 use proc_macro2::{Span, TokenStream};
 use proc_macro_error2::{emit_error, Diagnostic};
-use quote::{quote, quote_spanned, ToTokens};
+use quote::{quote_spanned, ToTokens};
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseStream},
     parse_quote,
     spanned::Spanned,
+    Token,
 };
 
 use crate::parse::{self, rollback_err};
@@ -62,6 +65,16 @@ impl Parse for Value {
         } else if input.peek(syn::token::Brace) {
             let (braces, tokens) = parse::braced_tokens(input).unwrap();
             Ok(Self::Block { tokens, braces })
+        // interpolated strings like `$"hello {name()}"`
+        } else if input.peek(Token![$]) && input.peek2(syn::LitStr) {
+            let dollar = <Token![$]>::parse(input).unwrap();
+            let lit = input.parse::<syn::LitStr>().unwrap();
+            // emit instead of returning the error, as this is definitely meant to be a
+            // value
+            Ok(Self::interpolated(dollar, &lit).unwrap_or_else(|e| {
+                emit_error!(e.span(), "{}", e);
+                Self::Lit(parse_quote!(""))
+            }))
         } else if input.peek(syn::Lit) {
             let lit = syn::Lit::parse(input).unwrap();
             Ok(Self::Lit(lit))
@@ -88,17 +101,7 @@ impl ToTokens for Value {
                 brackets,
             } => {
                 if let Some(prefixes) = prefixes {
-                    // only f[] is supported for now
-                    if prefixes == "f" {
-                        let format = quote_spanned!(prefixes.span()=> format!);
-                        quote_spanned!(brackets.span.join()=> move || ::std::#format(#tokens))
-                    } else {
-                        emit_error!(
-                            prefixes.span(),
-                            "unsupported prefix: only `f` is supported."
-                        );
-                        quote! {}
-                    }
+                    prefixed_bracket_tokens(prefixes, tokens, brackets.span.join())
                 } else {
                     quote_spanned!(brackets.span.join()=> move || {#tokens})
                 }
@@ -107,6 +110,56 @@ impl ToTokens for Value {
     }
 }
 
+/// Expands a bracketed value with a prefix, like `f["{}", a()]`.
+///
+/// The built-in prefixes are:
+/// - `f`: `move || format!(...)`
+/// - `d`: `Signal::derive(move || ...)`
+/// - `m`: `Memo::new(move |_| ...)`
+/// - `c`: `Callback::new(...)`, the brackets should contain a closure
+/// - `s`: `move || (...).to_string()`
+///
+/// Any other prefix is called as a function that is in scope, with the closure
+/// `move || ...` as its only argument. This allows other crates to provide
+/// their own prefixes, e.g. `my_prefix[a()]` expands to `my_prefix(move ||
+/// a())`. Single letters are reserved for the built-in prefixes, so that a
+/// typo like `x[...]` is reported as an unknown prefix instead of a missing
+/// function.
+fn prefixed_bracket_tokens(prefix: &syn::Ident, tokens: &TokenStream, span: Span) -> TokenStream {
+    let prefix_span = prefix.span();
+    match prefix.to_string().as_str() {
+        "f" => {
+            let format = quote_spanned!(prefix_span=> format!);
+            quote_spanned!(span=> move || ::std::#format(#tokens))
+        }
+        "d" => {
+            let derive = quote_spanned!(prefix_span=> ::leptos::prelude::Signal::derive);
+            quote_spanned!(span=> #derive(move || {#tokens}))
+        }
+        "m" => {
+            let memo = quote_spanned!(prefix_span=> ::leptos::prelude::Memo::new);
+            quote_spanned!(span=> #memo(move |_| {#tokens}))
+        }
+        "c" => {
+            let callback = quote_spanned!(prefix_span=> ::leptos::prelude::Callback::new);
+            quote_spanned!(span=> #callback(#tokens))
+        }
+        "s" => {
+            let to_string = quote_spanned!(prefix_span=> ::std::string::ToString::to_string);
+            quote_spanned!(span=> move || #to_string(&{#tokens}))
+        }
+        other if other.chars().count() == 1 => {
+            emit_error!(
+                prefix_span, "unknown prefix `{}`", other;
+                help = "the built-in prefixes are `f`, `d`, `m`, `c` and `s`. \
+                    Custom prefixes must be longer than one letter"
+            );
+            quote_spanned!(span=> move || {#tokens})
+        }
+        _ => quote_spanned!(span=> #prefix(move || {#tokens})),
+    }
+}
+
 impl Value {
     /// Returns the [`Span`] of this [`Value`].
     ///
@@ -150,10 +203,204 @@ impl Value {
         }
     }
 
+    /// Converts an interpolated string `$"..."` into a value.
+    ///
+    /// Each `{expr}` hole in the string becomes an argument to `format!`, so
+    /// `$"{a()} and {b}"` is the same as `f["{} and {}", a(), b]`. If there are
+    /// no holes, this is just a string literal. Use `{{` and `}}` for literal
+    /// braces.
+    fn interpolated(dollar: Token![$], lit: &syn::LitStr) -> syn::Result<Self> {
+        let span = lit.span();
+        let mut format_str = String::new();
+        let mut args = Vec::<TokenStream>::new();
+
+        let value = lit.value();
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    format_str.push_str("{{");
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    format_str.push_str("}}");
+                }
+                '{' => {
+                    let expr = take_hole(&mut chars).map_err(|msg| syn::Error::new(span, msg))?;
+                    let expr: TokenStream = expr.parse().map_err(|_| {
+                        syn::Error::new(span, format!("invalid expression `{expr}` in string"))
+                    })?;
+                    if expr.is_empty() {
+                        return Err(syn::Error::new(
+                            span,
+                            "empty `{}` in interpolated string: use `{{}}` for literal braces",
+                        ));
+                    }
+                    format_str.push_str("{}");
+                    args.push(respan(expr, span));
+                }
+                '}' => {
+                    return Err(syn::Error::new(
+                        span,
+                        "unmatched `}` in interpolated string: use `}}` for a literal brace",
+                    ))
+                }
+                c => format_str.push(c),
+            }
+        }
+
+        if args.is_empty() {
+            // unescape the braces
+            let unescaped = format_str.replace("{{", "{").replace("}}", "}");
+            Ok(Self::Lit(syn::Lit::Str(syn::LitStr::new(&unescaped, span))))
+        } else {
+            let format_str = syn::LitStr::new(&format_str, span);
+            Ok(Self::Bracket {
+                tokens: quote_spanned!(span=> #format_str, #(#args),*),
+                brackets: syn::token::Bracket(span),
+                prefixes: Some(syn::Ident::new("f", dollar.span)),
+            })
+        }
+    }
+
     /// Constructs self as a literal `true` with no span.
     pub fn new_true() -> Self { Self::Lit(parse_quote!(true)) }
 }
 
+/// Takes the expression in a `{...}` hole of an interpolated string, up to the
+/// matching `}`. The opening `{` should already be consumed.
+///
+/// Braces inside string and char literals are skipped. Errors if the hole is
+/// not closed, or if it has a format spec like `{x:?}`.
+///
+/// A `:` is only treated as the start of a format spec if it is outside of any
+/// delimiters and closure parameters, and is not part of a path separator `::`.
+fn take_hole(chars: &mut Peekable<Chars>) -> Result<String, &'static str> {
+    let mut expr = String::new();
+    let mut depth = 0_usize;
+    let mut in_closure_params = false;
+    loop {
+        let Some(c) = chars.next() else {
+            return Err("unclosed `{` in interpolated string");
+        };
+        match c {
+            '}' if depth == 0 => return Ok(expr),
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth = depth.saturating_sub(1),
+            // allow paths like `a::b`
+            ':' if chars.peek() == Some(&':') => {
+                expr.push(c);
+                expr.extend(chars.next());
+                continue;
+            }
+            // closure parameters like `|x: i32|`, but not `a | b` or `||`.
+            '|' if depth == 0 && in_closure_params => in_closure_params = false,
+            '|' if depth == 0 && chars.peek() != Some(&'|') && is_expr_start(&expr) => {
+                in_closure_params = true;
+            }
+            ':' if depth == 0 && !in_closure_params => {
+                return Err(
+                    "format specs like `:?` are not supported in interpolated strings: use the \
+                     `f` prefix instead",
+                )
+            }
+            '"' => {
+                expr.push(c);
+                take_str_lit(chars, &mut expr, 0)?;
+                continue;
+            }
+            'r' if is_raw_str_prefix(&expr) && matches!(chars.peek(), Some('"' | '#')) => {
+                // raw string: count the `#`s so that the end can be found.
+                expr.push(c);
+                let mut hashes = 0;
+                while chars.peek() == Some(&'#') {
+                    expr.extend(chars.next());
+                    hashes += 1;
+                }
+                if chars.peek() == Some(&'"') {
+                    expr.extend(chars.next());
+                    take_str_lit(chars, &mut expr, hashes)?;
+                }
+                continue;
+            }
+            // char literals like `'}'` or `'\''`, but not lifetimes like `'a`.
+            '\'' if chars.peek() == Some(&'\\') || chars.clone().nth(1) == Some('\'') => {
+                expr.push(c);
+                while let Some(c) = chars.next() {
+                    expr.push(c);
+                    match c {
+                        '\\' => expr.extend(chars.next()),
+                        '\'' => break,
+                        _ => (),
+                    }
+                }
+                continue;
+            }
+            _ => (),
+        }
+        expr.push(c);
+    }
+}
+
+/// Whether an `r` after `expr` starts a raw string literal, like `r"..."` or
+/// `br"..."`, instead of being part of an identifier.
+fn is_raw_str_prefix(expr: &str) -> bool {
+    let before = expr.strip_suffix(['b', 'c']).unwrap_or(expr);
+    !before.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+}
+
+/// Whether an expression can start after `expr`, such as a closure.
+fn is_expr_start(expr: &str) -> bool {
+    let expr = expr.trim_end();
+    expr.is_empty() || expr.ends_with(['(', '[', '{', ',', '=', '!']) || expr.ends_with("move")
+}
+
+/// Pushes the rest of a string literal onto `expr`, after its opening `"`.
+///
+/// `raw_hashes` is the number of `#`s for a raw string, which also means that
+/// `\` is not an escape.
+fn take_str_lit(
+    chars: &mut Peekable<Chars>,
+    expr: &mut String,
+    raw_hashes: usize,
+) -> Result<(), &'static str> {
+    let is_raw = raw_hashes > 0 || expr.ends_with("r\"");
+    while let Some(c) = chars.next() {
+        expr.push(c);
+        match c {
+            '\\' if !is_raw => expr.extend(chars.next()),
+            '"' => {
+                let closing = chars.clone().take_while(|c| *c == '#').count();
+                if closing >= raw_hashes {
+                    expr.extend(chars.take(raw_hashes));
+                    return Ok(());
+                }
+            }
+            _ => (),
+        }
+    }
+    Err("unclosed string literal in interpolated string")
+}
+
+/// Sets the span of every token in `tokens` to `span`.
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut tt| {
+            if let proc_macro2::TokenTree::Group(group) = &tt {
+                let mut new_group =
+                    proc_macro2::Group::new(group.delimiter(), respan(group.stream(), span));
+                new_group.set_span(span);
+                tt = proc_macro2::TokenTree::Group(new_group);
+            } else {
+                tt.set_span(span);
+            }
+            tt
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -177,11 +424,11 @@ mod tests {
     }
 
     impl ValueKind {
-        fn value_is(&self, value: Value) -> bool {
+        fn value_is(&self, value: &Value) -> bool {
             match self {
-                ValueKind::Lit => value.is_lit(),
-                ValueKind::Block => value.is_block(),
-                ValueKind::Bracket => value.is_bracketed(),
+                Self::Lit => value.is_lit(),
+                Self::Block => value.is_block(),
+                Self::Bracket => value.is_bracketed(),
             }
         }
     }
@@ -193,15 +440,73 @@ mod tests {
         exprs.insert("\"hi\"", ValueKind::Lit);
         exprs.insert("1", ValueKind::Lit);
         exprs.insert("true", ValueKind::Lit);
-        exprs.insert("{value}", ValueKind::Block);
+        exprs.insert("{ value }", ValueKind::Block);
         exprs.insert("{value; value2; value3}", ValueKind::Block);
         exprs.insert("[abc.get()]", ValueKind::Bracket);
         exprs.insert("{(aa,)}", ValueKind::Block);
         exprs.insert("[{a; b}]", ValueKind::Bracket);
+        exprs.insert("$\"{{hi}}\"", ValueKind::Lit);
+        exprs.insert("$\"hi {name()}\"", ValueKind::Bracket);
 
         for (expr, kind) in exprs {
             let value = syn::parse_str(expr).unwrap();
-            assert!(kind.value_is(value))
+            assert!(kind.value_is(&value));
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        let Value::Bracket {
+            tokens, prefixes, ..
+        } = syn::parse_str(r#"$"{{ {a.b()} is {if c { 1 } else { 2 }} }}""#).unwrap()
+        else {
+            panic!("expected interpolation to be bracketed");
+        };
+        assert_eq!(prefixes.unwrap().to_string(), "f");
+        assert_eq!(
+            tokens.to_string(),
+            r#""{{ {} is {} }}" , a . b () , if c { 1 } else { 2 }"#
+        );
+
+        let Value::Lit(syn::Lit::Str(lit)) = syn::parse_str(r#"$"{{static}}""#).unwrap() else {
+            panic!("expected static interpolation to be a literal");
+        };
+        assert_eq!(lit.value(), "{static}");
+
+        // braces inside string and char literals don't end the hole
+        let interpolated = r#"$"{format!(\"}}{}\", '{')} {'\\''} {r\"{\"} {Vec::<u8>::new()}""#;
+        let Value::Bracket { tokens, .. } = syn::parse_str(interpolated).unwrap() else {
+            panic!("expected interpolation to be bracketed");
+        };
+        assert_eq!(
+            tokens.to_string(),
+            r#""{} {} {} {}" , format ! ("}}{}" , '{') , '\'' , r"{" , Vec ::< u8 >:: new ()"#
+        );
+
+        let dollar = syn::parse_quote!($);
+        for valid in [
+            "{(|x: i32| x)(1)}",
+            "{a::b}",
+            "{a | b}",
+            "{|| 1}",
+            "{move |x: u8| x}",
+            "{<u8 as Default>::default()}",
+            "{br#\"}\"#.len()} {b\"}\".len()} {b'}'}",
+        ] {
+            let lit = syn::LitStr::new(valid, proc_macro2::Span::call_site());
+            Value::interpolated(dollar, &lit).unwrap();
+        }
+        for invalid in [
+            "{a",
+            "a}",
+            "{}",
+            "{a :?}",
+            "{x.len():>5}",
+            "{\"a}",
+            "{a | b :x}",
+        ] {
+            let lit = syn::LitStr::new(invalid, proc_macro2::Span::call_site());
+            assert!(Value::interpolated(dollar, &lit).is_err());
         }
     }
 }
@@ -7,18 +7,21 @@ use std::collections::HashMap;
 
 use proc_macro2::{Span, TokenStream};
 use proc_macro_error2::emit_error;
-use quote::{quote, quote_spanned};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{ext::IdentExt, parse_quote, parse_quote_spanned, spanned::Spanned};
 
 use crate::ast::{
     attribute::{directive::Directive, selector::SelectorShorthand},
-    Attr, Element, KebabIdent, KebabIdentOrStr, NodeChild, Tag, Value,
+    Attr, Element, Fragment, KebabIdent, KebabIdentOrStr, NodeChild, Tag, Value,
 };
 
 /// Functions for specific parts of an element's expansion.
 mod subroutines;
 #[allow(clippy::wildcard_imports)]
 use subroutines::*;
+/// Expansions for control flow children like `@if`.
+mod control_flow;
+pub use control_flow::*;
 /// Small helper functions for converting types or emitting errors.
 mod utils;
 #[allow(clippy::wildcard_imports)]
@@ -45,6 +48,7 @@ pub fn root_children_tokens<'a>(
     children: impl Iterator<Item = &'a NodeChild>,
     span: Span,
 ) -> TokenStream {
+    let children = child_views(children).into_iter().map(|(_, view)| view);
     quote_spanned! { span=>
         ::leptos::prelude::View::new((
             #( #children, )*
@@ -57,7 +61,10 @@ pub fn children_fragment_tokens<'a>(
     children: impl Iterator<Item = &'a NodeChild>,
     span: Span,
 ) -> TokenStream {
-    let children = children.collect::<Vec<_>>();
+    let children = child_views(children)
+        .into_iter()
+        .map(|(_, view)| view)
+        .collect::<Vec<_>>();
     let has_multiple_children = children.len() > 1;
 
     if has_multiple_children {
@@ -71,6 +78,103 @@ pub fn children_fragment_tokens<'a>(
     }
 }
 
+/// Converts a `_ (...)` group into a block containing a fragment of its
+/// children.
+///
+/// # Example
+/// ```ignore
+/// _ clone:name ("a" {name})
+/// ```
+/// Expands to:
+/// ```ignore
+/// {
+///     let name = name.clone();
+///     ("a", {name})
+/// }
+/// ```
+/// Empty groups expand to `()`.
+pub fn fragment_to_tokens(fragment: &Fragment) -> TokenStream {
+    let mut clones = TokenStream::new();
+    for attr in fragment.attrs().iter() {
+        match attr {
+            Attr::Directive(dir) if dir.dir == "clone" => {
+                clones.extend(component_clone_tokens(dir));
+            }
+            other => emit_error!(
+                other.span(),
+                "only `clone:` directives are supported on fragments"
+            ),
+        }
+    }
+
+    let children = fragment.children();
+    if let Some(slot) = children.slot_children().next() {
+        emit_error!(
+            slot.tag().span(),
+            "slots should be inside a parent that supports slots"
+        );
+    }
+    let fragment_tokens = if children.node_children().next().is_none() {
+        quote_spanned! { fragment.span()=> () }
+    } else {
+        children_fragment_tokens(children.node_children(), fragment.span())
+    };
+
+    quote! {
+        {
+            #clones
+            #fragment_tokens
+        }
+    }
+}
+
+/// Converts each child to tokens, along with the span of the child.
+///
+/// All the siblings after a `@let` are nested into a single block with the
+/// `let` statement, so that the binding is only visible to later siblings.
+///
+/// `@window` and `@document` listeners do not render anything, so they are
+/// added to the block of the next sibling (or the previous one, if they are
+/// last).
+///
+/// # Example
+/// ```ignore
+/// "a" @let b = 1; {b} "c"
+/// ```
+/// Gives the views:
+/// ```ignore
+/// "a",
+/// { let b = 1; ({b}, "c") }
+/// ```
+fn child_views<'a>(mut children: impl Iterator<Item = &'a NodeChild>) -> Vec<(Span, TokenStream)> {
+    let mut views = Vec::new();
+    let mut listeners = TokenStream::new();
+    while let Some(child) = children.next() {
+        match child {
+            NodeChild::Let(let_) => {
+                let rest = children_fragment_tokens(children, let_.span());
+                views.push((let_.span(), quote! { { #listeners #let_ #rest } }));
+                return views;
+            }
+            NodeChild::GlobalListener(listener) => listener.to_tokens(&mut listeners),
+            child if listeners.is_empty() => views.push((child.span(), quote! { #child })),
+            child => {
+                let listeners = std::mem::take(&mut listeners);
+                views.push((child.span(), quote! { { #listeners #child } }));
+            }
+        }
+    }
+
+    if !listeners.is_empty() {
+        if let Some((_, last)) = views.last_mut() {
+            *last = quote! { { #listeners #last } };
+        } else {
+            views.push((Span::call_site(), quote! { { #listeners } }));
+        }
+    }
+    views
+}
+
 /// Converts an xml (like html, svg or math) element to tokens.
 ///
 /// Returns `None` if the element is not an xml element (custom component).
@@ -101,15 +205,19 @@ pub fn children_fragment_tokens<'a>(
 /// ```
 pub fn xml_to_tokens(element: &Element) -> Option<TokenStream> {
     let tag_path = match element.tag() {
-        Tag::Component(..) => return None,
+        Tag::Component(..) | Tag::ComponentExpr { .. } => return None,
         Tag::Html(ident) => quote! { ::leptos::tachys::html::element::#ident() },
-        Tag::Svg(ident) => quote! { ::leptos::tachys::svg::element::#ident() },
-        Tag::Math(ident) => quote! { ::leptos::tachys::math::element::#ident() },
+        Tag::Svg(ident) => quote! { ::leptos::tachys::svg::#ident() },
+        Tag::Math(ident) => quote! { ::leptos::tachys::mathml::#ident() },
         Tag::WebComponent(ident) => {
             let ident = ident.to_lit_str();
             let custom = syn::Ident::new("custom", ident.span());
             quote! { ::leptos::tachys::html::element::#custom(#ident) }
         }
+        Tag::Dynamic { tag, expr } => {
+            let custom = syn::Ident::new("custom", tag.span);
+            quote! { ::leptos::tachys::html::element::#custom(#expr) }
+        }
     };
 
     // add selector-style ids/classes (div.some-class #some-id)
@@ -125,8 +233,10 @@ pub fn xml_to_tokens(element: &Element) -> Option<TokenStream> {
     for a in element.attrs().iter() {
         match a {
             Attr::Kv(attr) => attrs.extend(xml_kv_attribute_tokens(attr, element.tag().kind())),
-            Attr::Directive(dir) => directives.extend(xml_directive_tokens(dir)),
+            Attr::Directive(dir) => directives.extend(xml_directive_tokens(dir, element.tag())),
             Attr::Spread(spread) => spread_attrs.extend(xml_spread_tokens(spread)),
+            Attr::ClassList(list) => attrs.extend(xml_class_list_tokens(list)),
+            Attr::StyleList(list) => attrs.extend(xml_style_list_tokens(list)),
         }
     }
 
@@ -174,12 +284,26 @@ pub fn xml_to_tokens(element: &Element) -> Option<TokenStream> {
 /// #[component]
 /// pub fn Com(num: u32, text: String, children: Children) -> impl IntoView { ... }
 /// ```
+///
+/// Components from an expression (`@component {expr}`) are first stored in a
+/// variable, which is used in place of the path:
+/// ```ignore
+/// {
+///     let component = expr;
+///     leptos::component_view(&component, /* ... */)
+/// }
+/// ```
 #[allow(clippy::too_many_lines)]
 pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<TokenStream> {
-    let Tag::Component(path) = element.tag() else {
-        return None;
+    let (path, component_let) = match element.tag() {
+        Tag::Component(path) => (turbofishify(path.clone()).into_token_stream(), None),
+        Tag::ComponentExpr { component, expr } => {
+            let ident = syn::Ident::new("component", Span::mixed_site().located_at(component.span));
+            let component_let = quote_spanned! { component.span=> let #ident = {#expr}; };
+            (ident.into_token_stream(), Some(component_let))
+        }
+        _ => return None,
     };
-    let path = turbofishify(path.clone());
 
     // collect a bunch of info about the element attributes //
 
@@ -189,6 +313,8 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
     // the variables (idents) to clone before making children
     // in the form `let name = name.clone();`
     let mut clones = TokenStream::new();
+    // signals split by `bind:prop:split`, which are used by multiple props
+    let mut bind_lets = TokenStream::new();
 
     // shorthands are not supported on slots
     if IS_SLOT {
@@ -215,8 +341,9 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
                         directive_to_any_attr_path(&Directive {
                             dir: syn::Ident::new("class", dot_symbol.span),
                             key: KebabIdentOrStr::KebabIdent(class.clone()),
-                            modifier: None,
+                            modifiers: Vec::new(),
                             value: None,
+                            handler_args: None,
                         })
                         .expect("class directive is known"),
                     );
@@ -235,8 +362,9 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
                 directive_to_any_attr_path(&Directive {
                     dir: syn::Ident::new("attr", Span::call_site()),
                     key: parse_quote_spanned! { first_pound_symbol.span=> id },
-                    modifier: None,
+                    modifiers: Vec::new(),
                     value: Some(Value::Lit(parse_quote!(#joined_ids))),
+                    handler_args: None,
                 })
                 .expect("attr directive is known"),
             );
@@ -252,12 +380,31 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
                 directive_paths.push(component_spread_tokens(spread));
             }
         }
+        Attr::ClassList(list) => {
+            if IS_SLOT {
+                emit_error!(list.span(), "class lists are not supported on slots");
+            } else {
+                directive_paths.extend(component_class_list_paths(list));
+            }
+        }
+        Attr::StyleList(list) => {
+            if IS_SLOT {
+                emit_error!(list.span(), "style lists are not supported on slots");
+            } else {
+                directive_paths.extend(component_style_list_paths(list));
+            }
+        }
         Attr::Directive(dir) => match dir.dir.to_string().as_str() {
             // clone works on both components and slots
             "clone" => {
-                emit_error_if_modifier(dir.modifier.as_ref());
+                emit_error_if_modifier(&dir.modifiers);
                 clones.extend(component_clone_tokens(dir));
             }
+            "bind" if !IS_SLOT => {
+                let (bind_let, methods) = component_bind_tokens(dir);
+                bind_lets.extend(bind_let);
+                attrs.extend(methods);
+            }
             // slots support no other directives
             other if IS_SLOT => {
                 emit_error!(dir.dir.span(), "`{}:` is not supported on slots", other);
@@ -275,6 +422,7 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
     // convert the collected info into tokens //
 
     let children = element.children().map(|children| {
+        emit_error_if_raw(children.node_children());
         let mut it = children.node_children().peekable();
         // need to check that there are any element children at all,
         // as components that accept slots may not accept children.
@@ -314,7 +462,7 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
             }
         });
 
-        Some(quote! {
+        let view = quote! {
             ::leptos::component::component_view(
                 &#path,
                 #component_props_builder
@@ -324,6 +472,12 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
                     #build
             )
             #directive_paths
+        };
+
+        Some(if component_let.is_some() || !bind_lets.is_empty() {
+            quote! { { #component_let #bind_lets #view } }
+        } else {
+            view
         })
     }
 }
@@ -359,8 +513,13 @@ fn slots_to_tokens<'a>(children: impl Iterator<Item = &'a Element>) -> TokenStre
     // to a vec of the each slot's expansion.
     let mut slot_children = HashMap::<syn::Ident, Vec<TokenStream>>::new();
     for el in children {
-        let Tag::Component(path) = el.tag() else {
-            panic!("called `slots_to_tokens` on non-slot element")
+        let path = match el.tag() {
+            Tag::Component(path) => path,
+            Tag::ComponentExpr { component, .. } => {
+                emit_error!(component.span, "slots cannot be created from an expression");
+                continue;
+            }
+            _ => panic!("called `slots_to_tokens` on non-slot element"),
         };
         let slot_name = if let Some(ident) = path.get_ident() {
             ident.clone()
@@ -0,0 +1,486 @@
+//! Expansions for the `@`-prefixed children, mostly for control flow.
+
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error2::emit_error;
+use quote::{quote, quote_spanned};
+
+use super::{
+    children_fragment_tokens,
+    subroutines::event_listener_tokens,
+    utils::{either_wrapped, is_single_root_element},
+};
+use crate::ast::{
+    Await, Children, For, GlobalListener, If, IfCondition, Match, NodeChild, Raw, Value,
+};
+
+/// Converts the children of one branch into a single view.
+///
+/// Slots are not allowed in branches, and empty branches expand to `()`.
+fn branch_tokens(children: &Children, span: Span) -> TokenStream {
+    if let Some(slot) = children.slot_children().next() {
+        emit_error!(
+            slot.tag().span(),
+            "slots should be inside a parent that supports slots"
+        );
+    }
+
+    if children.node_children().next().is_none() {
+        quote_spanned! { span=> () }
+    } else {
+        children_fragment_tokens(children.node_children(), span)
+    }
+}
+
+/// Converts an `@if` chain into a reactive closure.
+///
+/// # Example
+/// ```ignore
+/// @if [a()] ("a") @else if let Some(b) = [b()] ({b}) @else ("c")
+/// ```
+/// Expands to:
+/// ```ignore
+/// move || if a() {
+///     EitherOf3::A("a")
+/// } else if let Some(b) = b() {
+///     EitherOf3::B({b})
+/// } else {
+///     EitherOf3::C("c")
+/// }
+/// ```
+///
+/// If there is no `@else`, an extra `else` branch is added that renders `()`.
+pub fn if_to_tokens(if_: &If) -> TokenStream {
+    // the implicit else branch counts as a branch too
+    let total = if_.branches().len() + 1;
+
+    let branches = if_.branches().iter().enumerate().map(|(i, branch)| {
+        let if_token = branch.if_token;
+        let condition = match &branch.condition {
+            IfCondition::Expr(expr) => expr.clone(),
+            IfCondition::Let {
+                let_token,
+                pat,
+                expr,
+            } => quote! { #let_token #pat = #expr },
+        };
+        let view = either_wrapped(i, total, branch_tokens(&branch.children, if_token.span));
+        quote! { #if_token #condition { #view } }
+    });
+
+    let else_view = if_.else_branch().map_or_else(
+        || either_wrapped(total - 1, total, quote! { () }),
+        |(else_token, children)| {
+            either_wrapped(total - 1, total, branch_tokens(children, else_token.span))
+        },
+    );
+    let else_token = if_
+        .else_branch()
+        .map_or_else(|| quote! { else }, |(else_token, _)| quote! { #else_token });
+
+    quote_spanned! { if_.span()=>
+        move || #(#branches)else* #else_token { #else_view }
+    }
+}
+
+/// Converts a `@match` into a reactive closure.
+///
+/// # Example
+/// ```ignore
+/// @match [state()] {
+///     State::Loading => ("loading"),
+///     State::Ready(x) => (strong({x})),
+/// }
+/// ```
+/// Expands to:
+/// ```ignore
+/// move || match state() {
+///     State::Loading => Either::Left("loading"),
+///     State::Ready(x) => Either::Right(strong().child({x})),
+/// }
+/// ```
+pub fn match_to_tokens(match_: &Match) -> TokenStream {
+    let match_token = match_.keyword();
+    let expr = match_.expr();
+    let total = match_.arms().len();
+
+    let arms = match_.arms().iter().enumerate().map(|(i, arm)| {
+        let pat = &arm.pat;
+        let fat_arrow = arm.fat_arrow;
+        let view = either_wrapped(i, total, branch_tokens(&arm.children, fat_arrow.spans[0]));
+        quote! { #pat #fat_arrow { #view } }
+    });
+
+    quote_spanned! { match_.span()=>
+        move || #match_token #expr { #(#arms)* }
+    }
+}
+
+/// Converts a `@for` loop into either a keyed `For` component or a collected
+/// iterator.
+///
+/// # Example
+/// ```ignore
+/// @for item in [items()] key={item.id} (li({item.name}))
+/// ```
+/// Expands to:
+/// ```ignore
+/// component_view(
+///     &For,
+///     component_props_builder(&For)
+///         .each(move || items())
+///         .key(move |item| item.id)
+///         .children(move |item| li().child({item.name}))
+///         .build(),
+/// )
+/// ```
+/// Unused variables are allowed, as the pattern is shared between the key and
+/// children.
+///
+/// Without a key, it expands to:
+/// ```ignore
+/// move || CollectView::collect_view(
+///     Iterator::map(IntoIterator::into_iter(items()), move |item| /* ... */)
+/// )
+/// ```
+/// If the iterable is in braces instead, the surrounding `move ||` is omitted.
+pub fn for_to_tokens(for_: &For) -> TokenStream {
+    let pat = for_.pat();
+    let children = branch_tokens(for_.children(), for_.span());
+
+    if let Some((key_token, key_expr)) = for_.key() {
+        let iter = for_.iterable();
+        let key = syn::Ident::new("key", key_token.span);
+        let for_path = quote_spanned! { for_.span()=> ::leptos::control_flow::For };
+        // the same pattern is used for both the key and children, which
+        // will usually only need some of the bindings each.
+        return quote! {
+            {
+                #[allow(unused_variables)]
+                let for_view = ::leptos::component::component_view(
+                    &#for_path,
+                    ::leptos::component::component_props_builder(&#for_path)
+                        .each(#iter)
+                        .#key(move |#pat| #key_expr)
+                        .children(move |#pat| #children)
+                        .build()
+                );
+                for_view
+            }
+        };
+    }
+
+    let (iter, reactive) = match for_.iterable() {
+        Value::Bracket {
+            tokens,
+            prefixes: None,
+            ..
+        } => (tokens, true),
+        Value::Block { tokens, .. } => (tokens, false),
+        other => {
+            emit_error!(
+                other.span(),
+                "expected an iterable wrapped in brackets `[...]` or braces `{...}`"
+            );
+            return quote! {};
+        }
+    };
+    let collected = quote_spanned! { for_.span()=>
+        ::leptos::prelude::CollectView::collect_view(
+            ::std::iter::Iterator::map(
+                ::std::iter::IntoIterator::into_iter(#iter),
+                move |#pat| #children
+            )
+        )
+    };
+    if reactive {
+        quote_spanned! { for_.span()=> move || #collected }
+    } else {
+        collected
+    }
+}
+
+/// Converts an `@await` into a `Suspense` that waits for a resource.
+///
+/// # Example
+/// ```ignore
+/// @await {fetch()} fallback=("loading...") |data| (p({data}))
+/// ```
+/// Expands to:
+/// ```ignore
+/// {
+///     let resource = ArcOnceResource::new(fetch());
+///     component_view(
+///         &Suspense,
+///         component_props_builder(&Suspense)
+///             .fallback(move || "loading...")
+///             .children(ToChildren::to_children(move || {
+///                 Suspend::new(async move {
+///                     let data = resource.await;
+///                     p().child({data})
+///                 })
+///             }))
+///             .build(),
+///     )
+/// }
+/// ```
+/// This is the same as what the `Await` component does, except the fallback is
+/// passed to the `Suspense`, and the value is cloned out of the resource.
+pub fn await_to_tokens(await_: &Await) -> TokenStream {
+    let span = await_.span();
+    let future = await_.future();
+    let resource = syn::Ident::new("resource", Span::mixed_site().located_at(span));
+    let pat = await_
+        .pat()
+        .map_or_else(|| quote_spanned! { span=> _ }, Clone::clone);
+    let keyword = await_.keyword();
+    let children = branch_tokens(await_.children(), span);
+
+    let fallback = await_.fallback().map(|(fallback_token, fallback)| {
+        let fallback_method = syn::Ident::new("fallback", fallback_token.span);
+        let fallback = branch_tokens(fallback, fallback_token.span);
+        quote! { .#fallback_method(move || #fallback) }
+    });
+
+    let suspense_path = quote_spanned! { span=> ::leptos::suspense::Suspense };
+    quote! {
+        {
+            let #resource = ::leptos::prelude::ArcOnceResource::new(#future);
+            ::leptos::component::component_view(
+                &#suspense_path,
+                ::leptos::component::component_props_builder(&#suspense_path)
+                    #fallback
+                    .children(::leptos::children::ToChildren::to_children(move || {
+                        ::leptos::prelude::Suspend::new(async move {
+                            let #pat = #resource.#keyword;
+                            #children
+                        })
+                    }))
+                    .build()
+            )
+        }
+    }
+}
+
+/// Converts a `@raw` child into an `InertElement`, or an element with
+/// `inner_html` for blocks and brackets.
+///
+/// # Example
+/// ```ignore
+/// @raw "<b>hi</b>"
+/// @raw [html()]
+/// @raw(section) {markdown}
+/// ```
+/// Expands to:
+/// ```ignore
+/// InertElement::new("<b>hi</b>")
+/// div().inner_html(move || html())
+/// section().inner_html({markdown})
+/// ```
+///
+/// `InertElement` does not support adding attributes, so see
+/// [`emit_error_if_raw`] for where these are not allowed.
+///
+/// It also only mounts the first element on the client, and panics when
+/// hydrating anything that isn't an element, so string literals must have
+/// exactly one root element. Other values may have any number of roots, so
+/// they are always rendered inside a wrapper element.
+pub fn raw_to_tokens(raw: &Raw) -> TokenStream {
+    let keyword = raw.keyword().span;
+    let inner_html = |value: TokenStream| {
+        let tag = raw
+            .tag()
+            .cloned()
+            .unwrap_or_else(|| syn::Ident::new("div", keyword));
+        let inner_html = syn::Ident::new("inner_html", keyword);
+        quote! { ::leptos::tachys::html::element::#tag().#inner_html(#value) }
+    };
+
+    match raw.value() {
+        Value::Lit(syn::Lit::Str(lit)) if raw.tag().is_some() => inner_html(quote! { #lit }),
+        Value::Lit(syn::Lit::Str(lit)) => {
+            if !is_single_root_element(&lit.value()) {
+                emit_error!(
+                    lit.span(), "`@raw` HTML must have exactly one root element";
+                    help = "wrap the HTML in an element, or name a wrapper with `@raw(div)`"
+                );
+            }
+            let new = quote_spanned! { keyword=> ::leptos::tachys::html::InertElement::new };
+            quote! { #new(#lit) }
+        }
+        Value::Lit(lit) => {
+            emit_error!(lit.span(), "expected a string literal");
+            quote! {}
+        }
+        Value::Block { tokens, .. } => inner_html(quote! { {#tokens} }),
+        Value::Bracket {
+            tokens,
+            prefixes: None,
+            brackets,
+        } => inner_html(quote_spanned! { brackets.span.join()=> move || {#tokens} }),
+        Value::Bracket {
+            prefixes: Some(prefix),
+            ..
+        } => {
+            emit_error!(prefix.span(), "prefixes are not supported on `@raw`");
+            quote! {}
+        }
+    }
+}
+
+/// Converts a `@window` or `@document` into a block that adds each event
+/// listener and removes it when the current owner is cleaned up.
+///
+/// The block evaluates to `()`, so it does not render anything.
+///
+/// # Example
+/// ```ignore
+/// @window on:resize={handle_resize};
+/// ```
+/// Expands to:
+/// ```ignore
+/// {
+///     {
+///         let handler = RefCell::new({handle_resize});
+///         let listener = window_event_listener(resize, move |ev| (*handler.borrow_mut())(ev));
+///         on_cleanup(move || listener.remove());
+///     }
+/// }
+/// ```
+/// The handler is wrapped in a `RefCell` as `window_event_listener` only takes
+/// `Fn` handlers, but modifiers like `:once` need `FnMut`.
+///
+/// There is no `document_event_listener`, so `@document` listeners are added
+/// in an `Effect` (so that they only run in the browser) with a
+/// `wasm_bindgen::Closure`.
+pub fn global_listener_to_tokens(global: &GlobalListener) -> TokenStream {
+    let target = global.target();
+    let is_window = target == "window";
+    let span = Span::mixed_site().located_at(global.span());
+    let handler_ident = syn::Ident::new("handler", span);
+    let listener_ident = syn::Ident::new("listener", span);
+
+    let listeners = global
+        .listeners()
+        .iter()
+        .filter(|dir| dir.dir == "on")
+        .map(|dir| {
+            for modifier in &dir.modifiers {
+                if ["capture", "passive", "undelegated"]
+                    .iter()
+                    .any(|m| modifier == m)
+                {
+                    emit_error!(
+                        modifier.span(),
+                        "`:{}` is not supported on `@{}`",
+                        modifier,
+                        target
+                    );
+                }
+            }
+            let (event, handler, ev_type) = event_listener_tokens(dir);
+
+            if is_window {
+                let window_event_listener = quote_spanned! { target.span()=>
+                    ::leptos::prelude::window_event_listener
+                };
+                return quote! {
+                    {
+                        let #handler_ident = ::std::cell::RefCell::new(#handler);
+                        let #listener_ident = #window_event_listener(
+                            #event,
+                            move |ev| (*#handler_ident.borrow_mut())(ev),
+                        );
+                        ::leptos::prelude::on_cleanup(move || #listener_ident.remove());
+                    }
+                };
+            }
+
+            let document = quote_spanned! { target.span()=> ::leptos::prelude::document };
+            quote! {
+                {
+                    let mut #handler_ident = ::std::option::Option::Some(#handler);
+                    ::leptos::prelude::Effect::new(move |_| {
+                        let ::std::option::Option::Some(mut #handler_ident) = #handler_ident.take()
+                        else {
+                            return;
+                        };
+                        let name = ::leptos::tachys::html::event::EventDescriptor::name(&#event);
+                        let closure = ::leptos::wasm_bindgen::closure::Closure::<
+                            dyn ::std::ops::FnMut(::leptos::web_sys::Event)
+                        >::new(move |ev: ::leptos::web_sys::Event| {
+                            let ev: #ev_type = ::leptos::wasm_bindgen::JsCast::unchecked_into(ev);
+                            #handler_ident(ev)
+                        });
+                        let document = #document();
+                        _ = document.add_event_listener_with_callback(
+                            &name,
+                            ::leptos::wasm_bindgen::JsCast::unchecked_ref(closure.as_ref()),
+                        );
+                        let #listener_ident =
+                            ::leptos::prelude::StoredValue::new_local((document, name, closure));
+                        ::leptos::prelude::on_cleanup(move || {
+                            ::leptos::prelude::WithValue::try_with_value(
+                                &#listener_ident,
+                                |(document, name, closure)| {
+                                    _ = document.remove_event_listener_with_callback(
+                                        name,
+                                        ::leptos::wasm_bindgen::JsCast::unchecked_ref(
+                                            closure.as_ref()
+                                        ),
+                                    );
+                                },
+                            );
+                        });
+                    });
+                }
+            }
+        });
+
+    quote! {
+        {
+            #(#listeners)*
+        }
+    }
+}
+
+/// Emits an error on any `@raw` string literals that may be returned at the
+/// top level of a view.
+///
+/// These are the direct children of components, and the root of the macro, as
+/// attributes may be added to them which `InertElement` does not support. The
+/// children of control flow and `_ (...)` fragments are also checked, as the
+/// attributes are passed on to them.
+pub fn emit_error_if_raw<'a>(children: impl Iterator<Item = &'a NodeChild>) {
+    for child in children {
+        match child {
+            NodeChild::Raw(raw) if raw.is_inert() => emit_error!(
+                raw.span(), "`@raw` cannot be used at the top level of a view";
+                help = "wrap it in an element, or name a wrapper with `@raw(div)`"
+            ),
+            NodeChild::Fragment(fragment) => {
+                emit_error_if_raw(fragment.children().node_children());
+            }
+            NodeChild::If(if_) => {
+                for branch in if_.branches() {
+                    emit_error_if_raw(branch.children.node_children());
+                }
+                if let Some((_, children)) = if_.else_branch() {
+                    emit_error_if_raw(children.node_children());
+                }
+            }
+            NodeChild::Match(match_) => {
+                for arm in match_.arms() {
+                    emit_error_if_raw(arm.children.node_children());
+                }
+            }
+            NodeChild::For(for_) => emit_error_if_raw(for_.children().node_children()),
+            NodeChild::Await(await_) => {
+                emit_error_if_raw(await_.children().node_children());
+                if let Some((_, children)) = await_.fallback() {
+                    emit_error_if_raw(children.node_children());
+                }
+            }
+            _ => (),
+        }
+    }
+}
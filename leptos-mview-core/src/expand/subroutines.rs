@@ -1,4 +1,4 @@
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Spacing, Span, TokenStream, TokenTree};
 use proc_macro_error2::emit_error;
 use quote::{quote, quote_spanned};
 use syn::{ext::IdentExt, spanned::Spanned};
@@ -6,14 +6,16 @@ use syn::{ext::IdentExt, spanned::Spanned};
 use crate::{
     ast::{
         attribute::{
+            class_list::{ClassEntry, ClassList},
             directive::Directive,
             kv::KvAttr,
             selector::{SelectorShorthand, SelectorShorthands},
             spread_attrs::SpreadAttr,
+            style_list::StyleList,
         },
-        KebabIdentOrStr, NodeChild, TagKind, Value,
+        KebabIdentOrStr, NodeChild, Tag, TagKind, Value,
     },
-    expand::{children_fragment_tokens, emit_error_if_modifier, utils},
+    expand::{child_views, children_fragment_tokens, emit_error_if_modifier, utils},
 };
 
 ////////////////////////////////////////////////////////////////
@@ -32,12 +34,13 @@ pub(super) fn use_directive_fn_value(u: &Directive) -> (syn::Ident, TokenStream)
     let Directive {
         dir: use_token,
         key,
-        modifier,
+        modifiers,
         value,
+        handler_args: _,
     } = u;
     assert_eq!(use_token, "use", "directive should be `use:`");
     let directive_fn = key.to_ident_or_emit();
-    emit_error_if_modifier(modifier.as_ref());
+    emit_error_if_modifier(modifiers);
 
     let value = value.as_ref().map_or_else(
         || quote_spanned! {directive_fn.span()=> ().into() },
@@ -46,12 +49,52 @@ pub(super) fn use_directive_fn_value(u: &Directive) -> (syn::Ident, TokenStream)
     (directive_fn, value)
 }
 
-pub(super) fn event_listener_event_path(dir: &Directive) -> TokenStream {
+/// Converts an `on:` directive into the event and handler that are passed to
+/// `on(event, handler)`, along with the type of the event passed to the
+/// handler.
+///
+/// Modifiers either change the event listener or wrap the handler:
+/// - `:undelegated`, `:capture` wrap the event in the matching tachys function.
+/// - `:passive` is handled by [`passive_listener_tokens`], which uses the event
+///   and handler from here.
+/// - `:prevent`, `:stop`, `:self` run `prevent_default()`, `stop_propagation()`
+///   or check the target before the handler, in the order they are written.
+/// - `:once` only runs the handler on the first event that passes the other
+///   guards.
+/// - `:async` spawns the future returned by the handler with `spawn_local`.
+/// - `:value`, `:checked` pass `event_target_value(&ev)` or
+///   `event_target_checked(&ev)` to the handler instead of the event, and
+///   `:value:parse` only calls the handler if the value parses successfully.
+/// - `:ctrl`, `:shift`, `:alt`, `:meta` only run the handler if the key is
+///   held, and on keyboard events, key names like `:enter` or `:s` only run the
+///   handler if `KeyboardEvent.key` matches (see [`key_filter_value`]). These
+///   are also checked in order with the other guards.
+///
+/// # Example
+/// ```ignore
+/// button on:click:prevent:self:once={handle_click};
+/// ```
+/// Expands to the event `click` and handler:
+/// ```ignore
+/// {
+///     let mut handler = Some({handle_click});
+///     move |ev: <click as EventDescriptor>::EventType| {
+///         ev.prevent_default();
+///         if ev.target() == ev.current_target() {
+///             if let Some(mut handler) = handler.take() { handler(ev) }
+///         }
+///     }
+/// }
+/// ```
+///
+/// **Panics** if the provided directive is not `on:`.
+pub(super) fn event_listener_tokens(dir: &Directive) -> (TokenStream, TokenStream, TokenStream) {
     let Directive {
         dir,
         key,
-        modifier,
-        value: _,
+        modifiers,
+        value,
+        handler_args,
     } = dir;
     assert_eq!(dir, "on", "directive should be `on:`");
 
@@ -62,24 +105,667 @@ pub(super) fn event_listener_event_path(dir: &Directive) -> TokenStream {
             syn::Ident::new("invalid_event", s.span())
         }
     };
+    let ev_path = quote! { ::leptos::tachys::html::event::#ev_name };
+    let ev_type = quote! {
+        <#ev_path as ::leptos::tachys::html::event::EventDescriptor>::EventType
+    };
 
-    if let Some(modifier) = modifier {
-        if modifier == "undelegated" {
+    let is_key_event = ev_name.unraw().to_string().starts_with("key");
+    let ev = syn::Ident::new("ev", Span::mixed_site());
+    let modifiers = EventModifiers::new(modifiers, &ev, is_key_event);
+
+    let mut event = ev_path;
+    for wrapper in &modifiers.event_wrappers {
+        event = quote! { ::leptos::tachys::html::event::#wrapper(#event) };
+    }
+
+    // the type of the argument passed to the handler
+    let arg_type = match modifiers.extract {
+        _ if modifiers.parse.is_some() => quote! { _ },
+        Some(extract) if extract == "checked" => quote! { bool },
+        Some(_) => quote! { ::std::string::String },
+        None => ev_type.clone(),
+    };
+    let handler = event_handler_tokens(
+        value.as_ref(),
+        handler_args.as_ref(),
+        &arg_type,
+        modifiers.spawn,
+    );
+    if modifiers.guards.is_empty() && !modifiers.once && modifiers.extract.is_none() {
+        return (event, handler, ev_type);
+    }
+
+    let handler_ident = syn::Ident::new("handler", Span::mixed_site());
+    let call = |arg: TokenStream| {
+        if modifiers.once {
             quote! {
-                ::leptos::tachys::html::event::#modifier(
-                    ::leptos::tachys::html::event::#ev_name
-                )
+                if let ::std::option::Option::Some(mut #handler_ident) = #handler_ident.take() {
+                    #handler_ident(#arg)
+                }
             }
         } else {
-            emit_error!(
-                modifier.span(), "unknown modifier";
-                help = ":undelegated is the only known modifier"
+            quote! { #handler_ident(#arg) }
+        }
+    };
+    let arg = match modifiers.extract {
+        Some(extract) if extract == "checked" => quote_spanned! { extract.span()=>
+            ::leptos::prelude::event_target_checked(&#ev)
+        },
+        Some(extract) => quote_spanned! { extract.span()=>
+            ::leptos::prelude::event_target_value(&#ev)
+        },
+        None => quote! { #ev },
+    };
+    let call = if let Some(parse) = modifiers.parse {
+        let parsed = syn::Ident::new("value", Span::mixed_site());
+        let call = call(quote! { #parsed });
+        quote_spanned! { parse.span()=>
+            if let ::std::result::Result::Ok(#parsed) = ::std::primitive::str::parse(&#arg) {
+                #call
+            }
+        }
+    } else {
+        call(arg)
+    };
+
+    let init = if modifiers.once {
+        quote! { ::std::option::Option::Some(#handler) }
+    } else {
+        handler
+    };
+    // nest the call inside the guards, so that `:once` is only used up by an
+    // event that passes all of them
+    let body = modifiers
+        .guards
+        .iter()
+        .rev()
+        .fold(call, |body, guard| match guard {
+            Guard::Run(stmt) => quote! { #stmt #body },
+            Guard::Check(cond) => quote! { if #cond { #body } },
+        });
+    let handler = quote! {
+        {
+            #[allow(unused_mut)]
+            let mut #handler_ident = #init;
+            move |#ev: #ev_type| {
+                #body
+            }
+        }
+    };
+    (event, handler, ev_type)
+}
+
+/// The modifiers on an `on:` directive, sorted by what they change.
+struct EventModifiers<'a> {
+    /// `:undelegated` and `:capture`, which wrap the event.
+    event_wrappers: Vec<&'a syn::Ident>,
+    once: bool,
+    /// `:async`
+    spawn: Option<&'a syn::Ident>,
+    /// `:value` or `:checked`
+    extract: Option<&'a syn::Ident>,
+    parse: Option<&'a syn::Ident>,
+    /// Checks and calls on the event `ev` to run before the handler, in the
+    /// order they were written.
+    guards: Vec<Guard>,
+}
+
+/// A modifier that runs before the handler.
+enum Guard {
+    /// A statement that is always run, like `ev.prevent_default();`.
+    Run(TokenStream),
+    /// A condition that must be true for the rest of the guards and the
+    /// handler to run.
+    Check(TokenStream),
+}
+
+impl<'a> EventModifiers<'a> {
+    fn new(modifiers: &'a [syn::Ident], ev: &syn::Ident, is_key_event: bool) -> Self {
+        let mut this = Self {
+            event_wrappers: Vec::new(),
+            once: false,
+            spawn: None,
+            extract: None,
+            parse: None,
+            guards: Vec::new(),
+        };
+
+        for modifier in modifiers {
+            match &*modifier.to_string() {
+                "undelegated" | "capture" => this.event_wrappers.push(modifier),
+                // see `passive_listener_tokens`
+                "passive" => (),
+                "once" => this.once = true,
+                "async" => this.spawn = Some(modifier),
+                "value" | "checked" => {
+                    if this.extract.replace(modifier).is_some() {
+                        emit_error!(
+                            modifier.span(),
+                            "only one of `:value` or `:checked` can be used"
+                        );
+                    }
+                }
+                "parse" => this.parse = Some(modifier),
+                "prevent" => this
+                    .guards
+                    .push(Guard::Run(quote_spanned! { modifier.span()=>
+                        #ev.prevent_default();
+                    })),
+                "stop" => this
+                    .guards
+                    .push(Guard::Run(quote_spanned! { modifier.span()=>
+                        #ev.stop_propagation();
+                    })),
+                "self" => this
+                    .guards
+                    .push(Guard::Check(quote_spanned! { modifier.span()=>
+                        #ev.target() == #ev.current_target()
+                    })),
+                "ctrl" | "shift" | "alt" | "meta" => {
+                    let key_method = syn::Ident::new(&format!("{modifier}_key"), modifier.span());
+                    this.guards
+                        .push(Guard::Check(quote_spanned! { modifier.span()=>
+                            #ev.#key_method()
+                        }));
+                }
+                other => {
+                    if let Some(key) = is_key_event.then(|| key_filter_value(other)).flatten() {
+                        this.guards
+                            .push(Guard::Check(quote_spanned! { modifier.span()=>
+                                #ev.key().eq_ignore_ascii_case(#key)
+                            }));
+                    } else {
+                        emit_error!(
+                            modifier.span(), "unknown modifier";
+                            help = "known modifiers are :prevent, :stop, :self, :once, \
+                                    :async, :value, :checked, :parse, :capture, :passive, \
+                                    :undelegated, :ctrl, :shift, :alt, :meta and key names on \
+                                    keyboard events"
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some(parse) = this.parse {
+            if !matches!(this.extract, Some(extract) if extract == "value") {
+                emit_error!(
+                    parse.span(), "`:parse` can only be used with `:value`";
+                    help = "try `:value:parse`"
+                );
+            }
+        }
+        this
+    }
+}
+
+/// Converts an `on:` directive with the `:passive` modifier into a directive
+/// attribute that adds the event listener itself, as tachys does not pass
+/// listener options on to `addEventListener`.
+///
+/// The listener is added with the `passive` option (and `capture` if
+/// `:capture` is also used) when the element is created, and removed when the
+/// current owner is cleaned up. Like other directives, this does nothing in
+/// SSR.
+///
+/// Returns [`None`] if the directive is not `:passive`.
+///
+/// # Example
+/// ```ignore
+/// div on:scroll:passive={handle_scroll};
+/// ```
+/// Expands to:
+/// ```ignore
+/// .add_any_attr({
+///     let handler = Cell::new(Some({handle_scroll}));
+///     directive(move |el: Element| {
+///         let Some(mut handler) = handler.take() else { return };
+///         let closure = Closure::new(move |ev: Event| handler(ev.unchecked_into()));
+///         let options = AddEventListenerOptions::new();
+///         options.set_passive(true);
+///         el.add_event_listener_with_callback_and_add_event_listener_options(
+///             "scroll", closure, &options,
+///         );
+///         on_cleanup(/* remove the listener */);
+///     }, ())
+/// })
+/// ```
+pub(super) fn passive_listener_tokens(dir: &Directive) -> Option<TokenStream> {
+    let passive = dir.modifiers.iter().find(|m| *m == "passive")?;
+    let capture = dir.modifiers.iter().any(|m| m == "capture");
+    let (event, handler, ev_type) = event_listener_tokens(dir);
+
+    let span = Span::mixed_site().located_at(passive.span());
+    let handler_ident = syn::Ident::new("handler", span);
+    let listener_ident = syn::Ident::new("listener", span);
+    let directive =
+        quote_spanned! { passive.span()=> ::leptos::tachys::html::directive::directive };
+    Some(quote! {
+        {
+            let #handler_ident = ::std::cell::Cell::new(::std::option::Option::Some(#handler));
+            #directive(
+                move |el: ::leptos::tachys::renderer::types::Element| {
+                    let ::std::option::Option::Some(mut #handler_ident) = #handler_ident.take()
+                    else {
+                        return;
+                    };
+                    let name = ::leptos::tachys::html::event::EventDescriptor::name(&#event);
+                    let closure = ::leptos::wasm_bindgen::closure::Closure::<
+                        dyn ::std::ops::FnMut(::leptos::web_sys::Event)
+                    >::new(move |ev: ::leptos::web_sys::Event| {
+                        let ev: #ev_type = ::leptos::wasm_bindgen::JsCast::unchecked_into(ev);
+                        #handler_ident(ev)
+                    });
+                    let options = ::leptos::web_sys::AddEventListenerOptions::new();
+                    options.set_passive(true);
+                    options.set_capture(#capture);
+                    _ = el.add_event_listener_with_callback_and_add_event_listener_options(
+                        &name,
+                        ::leptos::wasm_bindgen::JsCast::unchecked_ref(closure.as_ref()),
+                        &options,
+                    );
+                    let #listener_ident =
+                        ::leptos::prelude::StoredValue::new_local((el, name, closure));
+                    ::leptos::prelude::on_cleanup(move || {
+                        ::leptos::prelude::WithValue::try_with_value(
+                            &#listener_ident,
+                            |(el, name, closure)| {
+                                _ = el.remove_event_listener_with_callback_and_bool(
+                                    name,
+                                    ::leptos::wasm_bindgen::JsCast::unchecked_ref(
+                                        closure.as_ref()
+                                    ),
+                                    #capture,
+                                );
+                            },
+                        );
+                    });
+                },
+                (),
+            )
+        }
+    })
+}
+
+/// Converts a key filter modifier into the value of `KeyboardEvent.key`.
+///
+/// Single characters are used as is, digits are parsed as `digit0`-`digit9`,
+/// a few short names are aliases (like `esc` or `up`), and the other named
+/// keys are converted to upper camel case (`page_down` => `PageDown`). Returns
+/// [`None`] if the key is not known.
+fn key_filter_value(modifier: &str) -> Option<String> {
+    let key = match modifier {
+        "esc" => "Escape",
+        "space" => " ",
+        "up" => "ArrowUp",
+        "down" => "ArrowDown",
+        "left" => "ArrowLeft",
+        "right" => "ArrowRight",
+        "del" => "Delete",
+        "enter" | "tab" | "escape" | "backspace" | "delete" | "insert" | "home" | "end"
+        | "page_up" | "page_down" | "arrow_up" | "arrow_down" | "arrow_left" | "arrow_right"
+        | "caps_lock" | "context_menu" => {
+            let ident = syn::Ident::new(modifier, Span::call_site());
+            return Some(utils::snake_case_to_upper_camel(ident).unraw().to_string());
+        }
+        _ if modifier.chars().count() == 1 => modifier,
+        // `on:keydown:1`, see `parse_digit_modifier`
+        _ if modifier.len() == "digit0".len() && modifier.starts_with("digit") => {
+            let digit = &modifier["digit".len()..];
+            return digit
+                .chars()
+                .all(|c| c.is_ascii_digit())
+                .then(|| digit.to_string());
+        }
+        // function keys f1-f24
+        _ if modifier
+            .strip_prefix('f')
+            .and_then(|n| n.parse::<u8>().ok())
+            .is_some_and(|n| (1..=24).contains(&n)) =>
+        {
+            return Some(modifier.to_uppercase());
+        }
+        _ => return None,
+    };
+    Some(key.to_string())
+}
+
+/// Converts the value of an `on:` directive into the event handler.
+///
+/// Handlers in brackets take the event as an argument, so `on:click=[...]`
+/// expands to `move |_: ArgType| {...}` instead of `move || {...}`. The
+/// argument is named by the closure arguments in `on:click=|ev|[...]`. All
+/// other values are used as is.
+///
+/// If `spawn` is provided (from the `:async` modifier), the bracketed tokens
+/// become an `async move` block, or the future returned by any other handler is
+/// spawned with `spawn_local`.
+fn event_handler_tokens(
+    value: Option<&Value>,
+    args: Option<&TokenStream>,
+    arg_type: &TokenStream,
+    spawn: Option<&syn::Ident>,
+) -> TokenStream {
+    let spawn_local =
+        spawn.map(|spawn| quote_spanned! { spawn.span()=> ::leptos::task::spawn_local });
+    match (value, spawn_local) {
+        (
+            Some(Value::Bracket {
+                tokens,
+                brackets,
+                prefixes: None,
+            }),
+            spawn_local,
+        ) => {
+            let args = match args {
+                Some(args) if has_type_annotation(args) => args.clone(),
+                Some(args) => quote! { #args: #arg_type },
+                None => quote! { _: #arg_type },
+            };
+            let body = spawn_local.map_or_else(
+                || quote! { {#tokens} },
+                |spawn_local| quote! { { #spawn_local(async move {#tokens}); } },
             );
-            quote! { ::leptos::tachys::html::event::#ev_name }
+            quote_spanned! { brackets.span.join()=> move |#args| #body }
+        }
+        (other, None) => quote! { #other },
+        (other, Some(spawn_local)) => {
+            let ev = syn::Ident::new("ev", Span::mixed_site());
+            let handler = syn::Ident::new("handler", Span::mixed_site());
+            quote! {
+                {
+                    #[allow(unused_mut)]
+                    let mut #handler = #other;
+                    move |#ev: #arg_type| { #spawn_local(#handler(#ev)); }
+                }
+            }
+        }
+    }
+}
+
+/// Converts a `class=(...)` list into tachys class items, which are each
+/// passed to a separate `class` attribute.
+///
+/// Static strings are split into single classes and conditional classes use
+/// the `(name, condition)` tuple, which both add to the element's `classList`.
+/// A dynamic value is converted by [`class_names_tokens`] so that its classes
+/// are also toggled on the `classList` one by one, instead of setting the
+/// whole class string and removing the other classes.
+///
+/// # Example
+/// ```ignore
+/// class=("btn btn-lg", "active" => [is_active()], {extra})
+/// ```
+/// Expands to the items:
+/// ```ignore
+/// ("btn", true)
+/// ("btn-lg", true)
+/// ("active", move || is_active())
+/// { /* class_names_tokens */ }
+/// ```
+pub(super) fn class_list_items(list: &ClassList) -> Vec<TokenStream> {
+    let mut items = Vec::new();
+    for entry in list.entries() {
+        match entry {
+            ClassEntry::Static(classes) => {
+                items.extend(classes.value().split_whitespace().map(|class| {
+                    let class = syn::LitStr::new(class, classes.span());
+                    quote! { (#class, true) }
+                }));
+            }
+            ClassEntry::Conditional { name, condition } => {
+                items.push(quote! { (#name, #condition) });
+            }
+            ClassEntry::Dynamic(value) => {
+                items.push(class_names_tokens(value));
+            }
+        }
+    }
+    items
+}
+
+/// Converts the dynamic value of a class list into a class that adds each of
+/// its space-separated classes to the `classList` separately.
+///
+/// A block is converted to a string once, and a bracket is run in an effect
+/// that removes the classes it added before and adds the new ones. The type
+/// for this is declared in the expansion, so that it uses the `leptos` of the
+/// crate calling the macro.
+///
+/// # Example
+/// ```ignore
+/// class=([theme()])
+/// ```
+/// Expands to the item:
+/// ```ignore
+/// {
+///     let classes: Arc<dyn Fn() -> String + Send + Sync> =
+///         Arc::new(move || ToString::to_string(&theme()));
+///     {
+///         struct ClassNames(Arc<dyn Fn() -> String + Send + Sync>);
+///         impl IntoClass for ClassNames { ... }
+///         ClassNames(classes)
+///     }
+/// }
+/// ```
+fn class_names_tokens(value: &Value) -> TokenStream {
+    let classes = syn::Ident::new("classes", Span::mixed_site());
+    let to_string = quote! { ::std::string::ToString::to_string };
+    let classes_fn = if let Value::Block { .. } = value {
+        quote_spanned! { value.span()=>
+            {
+                let #classes = #to_string(&#value);
+                move || ::std::clone::Clone::clone(&#classes)
+            }
         }
     } else {
-        quote! { ::leptos::tachys::html::event::#ev_name }
+        quote_spanned! { value.span()=>
+            {
+                let #classes = #value;
+                move || #to_string(&#classes())
+            }
+        }
+    };
+
+    let string = quote! { ::std::string::String };
+    let classes_ty = quote! {
+        ::std::sync::Arc<
+            dyn ::std::ops::Fn() -> #string + ::std::marker::Send + ::std::marker::Sync
+        >
+    };
+    let class_list = quote! { ::leptos::tachys::renderer::types::ClassList };
+    let element = quote! { ::leptos::tachys::renderer::types::Element };
+    let rndr = quote! { ::leptos::tachys::renderer::Rndr };
+    let update_classes = update_classes_fn_tokens();
+    let effect = quote! { ::leptos::prelude::RenderEffect };
+    // the class list and the classes that were added.
+    let state_value = quote! { (#class_list, ::std::vec::Vec<#string>) };
+    let prev = quote! { prev: ::std::option::Option<#state_value> };
+
+    quote! {
+        {
+            let #classes: #classes_ty = ::std::sync::Arc::new(#classes_fn);
+            {
+                struct __MviewClassNames(#classes_ty);
+
+                impl ::std::clone::Clone for __MviewClassNames {
+                    fn clone(&self) -> Self { Self(::std::clone::Clone::clone(&self.0)) }
+                }
+
+                #update_classes
+
+                impl ::leptos::tachys::html::class::IntoClass for __MviewClassNames {
+                    type AsyncOutput = Self;
+                    type State = #effect<#state_value>;
+                    type Cloneable = Self;
+                    type CloneableOwned = Self;
+
+                    fn html_len(&self) -> usize { 0 }
+
+                    fn to_html(self, class: &mut #string) { class.push_str(&(self.0)()); }
+
+                    // adding a class that is already there does nothing.
+                    fn hydrate<const FROM_SERVER: bool>(self, el: &#element) -> Self::State {
+                        self.build(el)
+                    }
+
+                    fn build(self, el: &#element) -> Self::State {
+                        let class_list = #rndr::class_list(el);
+                        #effect::new(move |#prev| {
+                            let prev = prev.map(|(_, prev)| prev).unwrap_or_default();
+                            let classes =
+                                __mview_update_classes(&class_list, &prev, &(self.0)());
+                            (::std::clone::Clone::clone(&class_list), classes)
+                        })
+                    }
+
+                    fn rebuild(self, state: &mut Self::State) {
+                        *state = #effect::new_with_value(
+                            move |#prev| {
+                                let (class_list, prev) = prev.expect("classes should be built");
+                                let classes =
+                                    __mview_update_classes(&class_list, &prev, &(self.0)());
+                                (class_list, classes)
+                            },
+                            state.take_value(),
+                        );
+                    }
+
+                    fn into_cloneable(self) -> Self::Cloneable { self }
+
+                    fn into_cloneable_owned(self) -> Self::CloneableOwned { self }
+
+                    fn dry_resolve(&mut self) { (self.0)(); }
+
+                    async fn resolve(self) -> Self::AsyncOutput { self }
+
+                    fn reset(state: &mut Self::State) {
+                        *state = #effect::new_with_value(
+                            move |#prev| {
+                                let (class_list, prev) = prev.expect("classes should be built");
+                                __mview_update_classes(&class_list, &prev, "");
+                                (class_list, ::std::vec::Vec::new())
+                            },
+                            state.take_value(),
+                        );
+                    }
+                }
+
+                __MviewClassNames(#classes)
+            }
+        }
+    }
+}
+
+/// The function used by [`class_names_tokens`], which removes the `prev`
+/// classes that are not in `classes` and adds the new ones, returning the
+/// classes that are now added.
+fn update_classes_fn_tokens() -> TokenStream {
+    let string = quote! { ::std::string::String };
+    let class_list = quote! { ::leptos::tachys::renderer::types::ClassList };
+    let rndr = quote! { ::leptos::tachys::renderer::Rndr };
+    quote! {
+        fn __mview_update_classes(
+            class_list: &#class_list,
+            prev: &[#string],
+            classes: &str,
+        ) -> ::std::vec::Vec<#string> {
+            let classes = classes
+                .split_whitespace()
+                .map(::std::borrow::ToOwned::to_owned)
+                .collect::<::std::vec::Vec<#string>>();
+            for name in prev.iter().filter(|name| !classes.contains(name)) {
+                #rndr::remove_class(class_list, name);
+            }
+            for name in classes.iter().filter(|name| !prev.contains(name)) {
+                #rndr::add_class(class_list, name);
+            }
+            classes
+        }
+    }
+}
+
+/// Converts a `class:"a b"=[condition]` directive with multiple classes into a
+/// tuple of `class` attributes, one for each class, as the `classList` cannot
+/// add a name with spaces.
+///
+/// A bracketed condition is only run once in a single `Memo` that all the
+/// classes share. Any other value is cloned for each class.
+///
+/// Returns [`None`] if the directive is not `class:` or the key is only a
+/// single class.
+///
+/// # Example
+/// ```ignore
+/// div class:"bg-red-500 text-white"=[err()];
+/// ```
+/// Expands to the attribute:
+/// ```ignore
+/// {
+///     let condition = Memo::new(move |_| err());
+///     (
+///         class(("bg-red-500", move || Get::get(&condition))),
+///         class(("text-white", move || Get::get(&condition))),
+///     )
+/// }
+/// ```
+pub(super) fn class_toggles_tokens(directive: &Directive) -> Option<TokenStream> {
+    let key = directive.key.to_unspanned_string();
+    let classes = key.split_whitespace().collect::<Vec<_>>();
+    if directive.dir != "class" || classes.len() <= 1 {
+        return None;
     }
+
+    let condition = syn::Ident::new("condition", Span::mixed_site());
+    let (condition_let, condition_value) = match &directive.value {
+        Some(Value::Bracket {
+            tokens,
+            brackets,
+            prefixes: None,
+        }) => (
+            quote_spanned! { brackets.span.join()=>
+                let #condition = ::leptos::prelude::Memo::new(move |_| {#tokens});
+            },
+            quote! { move || ::leptos::prelude::Get::get(&#condition) },
+        ),
+        value => {
+            let value = value.clone().unwrap_or_else(Value::new_true);
+            (
+                quote! { let #condition = #value; },
+                quote! { ::std::clone::Clone::clone(&#condition) },
+            )
+        }
+    };
+
+    let class = syn::Ident::new("class", directive.dir.span());
+    Some(quote! {
+        {
+            #condition_let
+            (
+                #(
+                    ::leptos::tachys::html::class::#class((#classes, #condition_value)),
+                )*
+            )
+        }
+    })
+}
+
+/// Whether the closure arguments already have a type, like `n: i32`.
+///
+/// This looks for a single `:` that is not part of a path separator `::`.
+fn has_type_annotation(args: &TokenStream) -> bool {
+    let mut after_joint_colon = false;
+    args.clone().into_iter().any(|tt| {
+        let TokenTree::Punct(punct) = tt else {
+            after_joint_colon = false;
+            return false;
+        };
+        let is_colon = punct.as_char() == ':';
+        let is_annotation = is_colon && punct.spacing() == Spacing::Alone && !after_joint_colon;
+        after_joint_colon = is_colon && punct.spacing() == Spacing::Joint;
+        is_annotation
+    })
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -153,10 +839,13 @@ pub(super) fn xml_kv_attribute_tokens(attr: &KvAttr, element_tag: TagKind) -> To
         // https://github.com/leptos-rs/leptos/blob/main/leptos_macro/src/view/mod.rs#L960
         // Use unchecked attributes if:
         // - it's not `class` nor `style`, and
-        // - It's a custom web component or SVG element
+        // - It's a custom web component, dynamic tag or SVG element
         // - or it's a custom or data attribute (has `-` except for `aria-`)
         let attr_kind = AttributeKind::from(key.repr());
-        let is_web_or_svg = matches!(element_tag, TagKind::Svg | TagKind::WebComponent);
+        let is_web_or_svg = matches!(
+            element_tag,
+            TagKind::Svg | TagKind::WebComponent | TagKind::Dynamic
+        );
 
         if (is_web_or_svg || attr_kind.is_custom()) && !attr_kind.is_class_or_style() {
             // unchecked attribute
@@ -172,28 +861,60 @@ pub(super) fn xml_kv_attribute_tokens(attr: &KvAttr, element_tag: TagKind) -> To
     }
 }
 
-pub(super) fn xml_directive_tokens(directive: &Directive) -> TokenStream {
+/// Converts a `class=(...)` list into a `.class(...)` call for each item.
+pub(super) fn xml_class_list_tokens(list: &ClassList) -> TokenStream {
+    let class = syn::Ident::new("class", list.key().span);
+    let items = class_list_items(list);
+    quote! { #(.#class(#items))* }
+}
+
+/// Converts a `style=(...)` list into a `.style((property, value))` call for
+/// each property, so that each one updates independently.
+pub(super) fn xml_style_list_tokens(list: &StyleList) -> TokenStream {
+    let style = syn::Ident::new("style", list.key().span);
+    let properties = list.entries().iter().map(|entry| {
+        let (property, value) = (entry.property().repr(), entry.value());
+        quote! { .#style((#property, #value)) }
+    });
+    quote! { #(#properties)* }
+}
+
+pub(super) fn xml_directive_tokens(directive: &Directive, element_tag: &Tag) -> TokenStream {
     let Directive {
         dir,
         key,
-        modifier,
+        modifiers,
         value,
+        handler_args: _,
     } = directive;
 
     match dir.to_string().as_str() {
         "class" | "style" => {
-            let key = key.to_lit_str();
-            emit_error_if_modifier(modifier.as_ref());
-            quote! { .#dir((#key, #value)) }
+            emit_error_if_modifier(modifiers);
+            if let Some(toggles) = class_toggles_tokens(directive) {
+                let add_any_attr = syn::Ident::new("add_any_attr", dir.span());
+                quote! { .#add_any_attr(#toggles) }
+            } else {
+                let key = key.to_lit_str();
+                quote! { .#dir((#key, #value)) }
+            }
         }
         "prop" => {
             let key = key.to_lit_str();
-            emit_error_if_modifier(modifier.as_ref());
+            emit_error_if_modifier(modifiers);
             quote! { .#dir(#key, #value) }
         }
+        "on" if matches!(&*key.to_unspanned_string(), "mount" | "cleanup" | "unmount") => {
+            let node_ref = syn::Ident::new("node_ref", dir.span());
+            let hook = lifecycle_hook_tokens(directive, element_tag);
+            quote! { .#node_ref(#hook) }
+        }
         "on" => {
-            let event_path = event_listener_event_path(directive);
-            quote! { .#dir(#event_path, #value) }
+            if let Some(listener) = passive_listener_tokens(directive) {
+                return quote! { .add_any_attr(#listener) };
+            }
+            let (event, handler, _) = event_listener_tokens(directive);
+            quote! { .#dir(#event, #handler) }
         }
         "use" => {
             let (fn_name, value) = use_directive_fn_value(directive);
@@ -206,19 +927,7 @@ pub(super) fn xml_directive_tokens(directive: &Directive) -> TokenStream {
             emit_error!(dir.span(), "`{}:` is not supported on elements", dir);
             quote! {}
         }
-        "bind" => {
-            emit_error_if_modifier(modifier.as_ref());
-            let bind = syn::Ident::new("bind", dir.span());
-            let bound_attribute_name = utils::snake_case_to_upper_camel(key.to_ident_or_emit());
-
-            // https://github.com/leptos-rs/leptos/pull/3680/files
-            // special case for `bind:group`
-            if key.to_lit_str().value() == "group" {
-                quote! { .#bind(::leptos::tachys::reactive_graph::bind::#bound_attribute_name, #value) }
-            } else {
-                quote! { .#bind(::leptos::attr::#bound_attribute_name, #value) }
-            }
-        }
+        "bind" => bind_directive_tokens(directive, element_tag),
         _ => {
             emit_error!(dir.span(), "unknown directive");
             quote! {}
@@ -226,6 +935,303 @@ pub(super) fn xml_directive_tokens(directive: &Directive) -> TokenStream {
     }
 }
 
+/// Converts a `bind:` directive into a two-way binding.
+///
+/// Without modifiers, this uses the tachys `bind` attribute, which already
+/// listens to the right event for `input`, `textarea` and `select` elements.
+/// Otherwise, the text of the element is synced manually:
+/// - `:lazy` listens to `change` instead of `input`.
+/// - `:trim` trims the text before setting the signal.
+/// - `:number` parses the text, and only sets the signal if it parses.
+///
+/// The signal is only updated if the new value is different, so that the
+/// element is not changed while typing (e.g. trailing spaces with `:trim`).
+///
+/// # Example
+/// ```ignore
+/// input bind:value:trim={name};
+/// ```
+/// Expands to:
+/// ```ignore
+/// .add_any_attr({
+///     let (read, write) = IntoSplitSignal::into_split_signal({name});
+///     (
+///         prop("value", move || Get::get(&read)),
+///         on(input, move |ev: Event| {
+///             let text: String = event_target_value(&ev);
+///             let text = text.trim();
+///             Update::maybe_update(&write, |current| { /* set if different */ });
+///         }),
+///     )
+/// })
+/// ```
+///
+/// `bind:innerText` (for `contenteditable` elements) is always synced
+/// manually, but with an `Effect` on a node ref instead of `prop`, so that the
+/// text (and cursor) is only replaced if it is different from the signal.
+fn bind_directive_tokens(directive: &Directive, element_tag: &Tag) -> TokenStream {
+    let Directive {
+        dir,
+        key,
+        modifiers,
+        value,
+        handler_args: _,
+    } = directive;
+    let key_str = key.to_unspanned_string();
+    let is_inner_text = matches!(&*key_str, "innerText" | "inner_text");
+
+    if modifiers.is_empty() && !is_inner_text {
+        let bind = syn::Ident::new("bind", dir.span());
+        let bound_attribute_name = utils::snake_case_to_upper_camel(key.to_ident_or_emit());
+
+        // https://github.com/leptos-rs/leptos/pull/3680/files
+        // special case for `bind:group`
+        return if key_str == "group" {
+            quote! { .#bind(::leptos::tachys::reactive_graph::bind::#bound_attribute_name, #value) }
+        } else {
+            quote! { .#bind(::leptos::attr::#bound_attribute_name, #value) }
+        };
+    }
+    if !is_inner_text && key_str != "value" {
+        emit_error!(
+            modifiers[0].span(),
+            "modifiers can only be used on `bind:value` and `bind:innerText`"
+        );
+        return quote! {};
+    }
+
+    let (mut lazy, mut trim, mut number) = (None, false, false);
+    for modifier in modifiers {
+        match &*modifier.to_string() {
+            "lazy" if is_inner_text => emit_error!(
+                modifier.span(),
+                "`:lazy` can only be used on `bind:value`";
+                note = "`contenteditable` elements do not fire `change` events"
+            ),
+            "lazy" => lazy = Some(modifier),
+            "trim" => trim = true,
+            "number" => number = true,
+            _ => emit_error!(
+                modifier.span(), "unknown modifier";
+                help = "known modifiers on `bind:` are :lazy, :trim and :number"
+            ),
+        }
+    }
+
+    let inner_text = is_inner_text.then_some(element_tag);
+    let attrs = bind_text_tokens(value.as_ref(), lazy, trim, number, inner_text);
+    let add_any_attr = syn::Ident::new("add_any_attr", dir.span());
+    quote! { .#add_any_attr(#attrs) }
+}
+
+/// The attributes that sync the text of an element with a signal, for a
+/// `bind:` directive with modifiers or `bind:innerText` (if `inner_text` is
+/// provided). See [`bind_directive_tokens`].
+fn bind_text_tokens(
+    value: Option<&Value>,
+    lazy: Option<&syn::Ident>,
+    trim: bool,
+    number: bool,
+    inner_text: Option<&Tag>,
+) -> TokenStream {
+    let [read, write, text, new, current, node_ref, el] =
+        ["read", "write", "text", "new", "current", "node_ref", "el"]
+            .map(|s| syn::Ident::new(s, Span::mixed_site()));
+    let event = lazy.map_or_else(
+        || quote! { ::leptos::tachys::html::event::input },
+        |lazy| quote_spanned! { lazy.span()=> ::leptos::tachys::html::event::change },
+    );
+    let ev_type = quote! {
+        <#event as ::leptos::tachys::html::event::EventDescriptor>::EventType
+    };
+
+    let trim = trim.then(|| quote! { let #text = #text.trim(); });
+    let set_signal = if number {
+        quote! {
+            if let ::std::result::Result::Ok(#new) = ::std::primitive::str::parse(#text) {
+                ::leptos::prelude::Update::maybe_update(&#write, |#current| {
+                    let changed = *#current != #new;
+                    if changed { *#current = #new; }
+                    changed
+                });
+            }
+        }
+    } else {
+        quote! {
+            ::leptos::prelude::Update::maybe_update(&#write, |#current| {
+                let changed = *#current != *#text;
+                if changed { *#current = ::std::borrow::ToOwned::to_owned(#text); }
+                changed
+            });
+        }
+    };
+    let signal_text = if number {
+        quote! { ::std::string::ToString::to_string(&::leptos::prelude::Get::get(&#read)) }
+    } else {
+        quote! { ::leptos::prelude::Get::get(&#read) }
+    };
+    let split = quote! {
+        let (#read, #write) =
+            ::leptos::tachys::reactive_graph::bind::IntoSplitSignal::into_split_signal(#value);
+    };
+
+    if let Some(element_tag) = inner_text {
+        let (element_type, element_output) = element_type_tokens(element_tag);
+        quote! {
+            {
+                #split
+                let #node_ref = ::leptos::prelude::NodeRef::<#element_type>::new();
+                ::leptos::prelude::Effect::new(move |_| {
+                    let #text = #signal_text;
+                    if let ::std::option::Option::Some(#el) = ::leptos::prelude::Get::get(&#node_ref) {
+                        let #el: #element_output = #el;
+                        if #el.inner_text() != #text {
+                            #el.set_inner_text(&#text);
+                        }
+                    }
+                });
+                (
+                    ::leptos::tachys::html::node_ref::node_ref(#node_ref),
+                    ::leptos::tachys::html::event::on(#event, move |_: #ev_type| {
+                        if let ::std::option::Option::Some(#el) =
+                            ::leptos::prelude::GetUntracked::get_untracked(&#node_ref)
+                        {
+                            let #el: #element_output = #el;
+                            let #text: ::std::string::String = #el.inner_text();
+                            let #text = #text.as_str();
+                            #trim
+                            #set_signal
+                        }
+                    }),
+                )
+            }
+        }
+    } else {
+        let ev = syn::Ident::new("ev", Span::mixed_site());
+        quote! {
+            {
+                #split
+                (
+                    ::leptos::tachys::html::property::prop("value", move || #signal_text),
+                    ::leptos::tachys::html::event::on(#event, move |#ev: #ev_type| {
+                        let #text: ::std::string::String = ::leptos::prelude::event_target_value(&#ev);
+                        let #text = #text.as_str();
+                        #trim
+                        #set_signal
+                    }),
+                )
+            }
+        }
+    }
+}
+
+/// Converts an `on:mount` or `on:cleanup` (`on:unmount`) pseudo-event into a
+/// new node ref, which runs the handler with the element once it is loaded.
+///
+/// `on:cleanup` runs the handler when the owner of the element is cleaned up
+/// instead. The element is kept in a local `StoredValue` until then, as the
+/// `on_cleanup` callback must be `Send + Sync`.
+///
+/// # Example
+/// ```ignore
+/// input on:mount=|el|[el.focus()];
+/// ```
+/// Expands to the node ref:
+/// ```ignore
+/// {
+///     let node_ref = NodeRef::<Input>::new();
+///     node_ref.on_load(move |el: <Input as ElementType>::Output| { el.focus() });
+///     node_ref
+/// }
+/// ```
+fn lifecycle_hook_tokens(directive: &Directive, element_tag: &Tag) -> TokenStream {
+    let Directive {
+        dir: _,
+        key,
+        modifiers,
+        value,
+        handler_args,
+    } = directive;
+    let hook = key.to_unspanned_string();
+    emit_error_if_modifier(modifiers);
+    if value.is_none() {
+        emit_error!(
+            key.to_lit_str().span(),
+            "expected a handler for `on:{}`",
+            hook
+        );
+    }
+
+    let (element_type, el_type) = element_type_tokens(element_tag);
+    let handler = event_handler_tokens(value.as_ref(), handler_args.as_ref(), &el_type, None);
+
+    let node_ref = syn::Ident::new("node_ref", Span::mixed_site());
+    let on_load = if hook == "mount" {
+        quote! { #node_ref.on_load(#handler); }
+    } else {
+        let (handler_ident, el, cleanup) = (
+            syn::Ident::new("handler", Span::mixed_site()),
+            syn::Ident::new("el", Span::mixed_site()),
+            syn::Ident::new("cleanup", Span::mixed_site()),
+        );
+        quote! {
+            // boxed so that closures without an annotated argument get their type
+            let #handler_ident: ::std::boxed::Box<dyn ::std::ops::FnOnce(#el_type)> =
+                ::std::boxed::Box::new(#handler);
+            #node_ref.on_load(move |#el| {
+                let #cleanup = ::leptos::prelude::StoredValue::new_local(
+                    ::std::option::Option::Some((#handler_ident, #el))
+                );
+                ::leptos::prelude::on_cleanup(move || {
+                    if let ::std::option::Option::Some(::std::option::Option::Some((#handler_ident, #el))) =
+                        ::leptos::prelude::UpdateValue::try_update_value(
+                            &#cleanup,
+                            ::std::option::Option::take,
+                        )
+                    {
+                        #handler_ident(#el);
+                    }
+                });
+            });
+        }
+    };
+
+    quote! {
+        {
+            let #node_ref = ::leptos::prelude::NodeRef::<#element_type>::new();
+            #on_load
+            #node_ref
+        }
+    }
+}
+
+/// Returns the tachys element type of a tag, along with the type of the
+/// element it creates (`<Div as ElementType>::Output`).
+///
+/// The type of a dynamic tag is only inferred later, so its element type is
+/// given directly: custom elements are always an `HtmlElement`.
+fn element_type_tokens(element_tag: &Tag) -> (TokenStream, TokenStream) {
+    let html = quote! { ::leptos::tachys::html::element };
+    let element_type = |module: TokenStream, ident: &syn::Ident| {
+        let ident = utils::snake_case_to_upper_camel(ident.clone());
+        let element_type = quote! { #module::#ident };
+        let el_type = quote! { <#element_type as #html::ElementType>::Output };
+        (element_type, el_type)
+    };
+    match element_tag {
+        Tag::Html(ident) => element_type(html.clone(), ident),
+        Tag::Svg(ident) => element_type(quote! { ::leptos::tachys::svg }, ident),
+        Tag::Math(ident) => element_type(quote! { ::leptos::tachys::mathml }, ident),
+        Tag::WebComponent(_) | Tag::Dynamic { .. } => (
+            quote! { #html::Custom<_> },
+            quote! { ::leptos::web_sys::HtmlElement },
+        ),
+        Tag::Component(..) | Tag::ComponentExpr { .. } => {
+            unreachable!("components should not use xml directives")
+        }
+    }
+}
+
 pub(super) fn xml_spread_tokens(attr: &SpreadAttr) -> TokenStream {
     let (dotdot, expr) = (attr.dotdot(), attr.expr());
     let attrs = syn::Ident::new("add_any_attr", dotdot.span());
@@ -244,12 +1250,15 @@ pub(super) fn xml_spread_tokens(attr: &SpreadAttr) -> TokenStream {
 /// ```ignore
 /// div().child("a").child({var}).child("b")
 /// ```
+///
+/// Any siblings after a `@let` are nested into the same `.child(...)` call,
+/// see `child_views`.
 pub(super) fn xml_child_methods_tokens<'a>(
     children: impl Iterator<Item = &'a NodeChild>,
 ) -> TokenStream {
     let mut ts = TokenStream::new();
-    for child in children {
-        let child_method = syn::Ident::new("child", child.span());
+    for (span, child) in child_views(children) {
+        let child_method = syn::Ident::new("child", span);
         ts.extend(quote! {
             .#child_method(#child)
         });
@@ -269,7 +1278,7 @@ pub(super) fn component_kv_attribute_tokens(attr: &KvAttr) -> TokenStream {
 /// Expands to a `let` statement `let to_clone = to_clone.clone();`.
 pub(super) fn component_clone_tokens(dir: &Directive) -> TokenStream {
     let to_clone = dir.key.to_ident_or_emit();
-    emit_error_if_modifier(dir.modifier.as_ref());
+    emit_error_if_modifier(&dir.modifiers);
     if let Some(value) = &dir.value {
         emit_error!(value.span(), "`clone:` does not take any values");
     };
@@ -277,6 +1286,82 @@ pub(super) fn component_clone_tokens(dir: &Directive) -> TokenStream {
     quote! { let #to_clone = #to_clone.clone(); }
 }
 
+/// Converts a `bind:prop={signal}` on a component into builder methods, along
+/// with any `let` statements that need to come before the component.
+///
+/// By default, the signal is passed to the prop as is: `.prop(signal)`.
+///
+/// With the `:split` modifier, the signal is split into its read and write
+/// halves, which are passed to `.prop(read)` and `.on_prop_change(callback)`
+/// respectively, where the callback is a `Callback<T>` that sets the signal.
+/// The split happens before the component, so that the signal is only
+/// evaluated once.
+///
+/// # Example
+/// ```ignore
+/// TextField bind:value:split={name};
+/// ```
+/// Expands to:
+/// ```ignore
+/// let (value_read, value_write) = IntoSplitSignal::into_split_signal({name});
+/// // ...
+/// component_props_builder(&TextField)
+///     .value(value_read)
+///     .on_value_change(Callback::new(move |value| {
+///         Update::update(&value_write, |current| *current = value);
+///     }))
+/// ```
+pub(super) fn component_bind_tokens(dir: &Directive) -> (TokenStream, TokenStream) {
+    let Directive {
+        dir,
+        key,
+        modifiers,
+        value,
+        handler_args: _,
+    } = dir;
+    let prop = key.to_ident_or_emit();
+    let Some(value) = value else {
+        emit_error!(dir.span(), "expected a signal to bind to");
+        return (quote! {}, quote! {});
+    };
+
+    let mut split = None;
+    for modifier in modifiers {
+        if modifier == "split" {
+            split = Some(modifier);
+        } else {
+            emit_error!(
+                modifier.span(), "unknown modifier";
+                help = "`bind:` on components only supports :split"
+            );
+        }
+    }
+    let Some(split) = split else {
+        return (quote! {}, quote! { .#prop(#value) });
+    };
+
+    let prop_name = prop.unraw();
+    let read = syn::Ident::new(&format!("{prop_name}_read"), Span::mixed_site());
+    let write = syn::Ident::new(&format!("{prop_name}_write"), Span::mixed_site());
+    let on_change = syn::Ident::new(&format!("on_{prop_name}_change"), prop.span());
+    let (new, current) = (
+        syn::Ident::new("value", Span::mixed_site()),
+        syn::Ident::new("current", Span::mixed_site()),
+    );
+
+    let split_let = quote_spanned! { split.span()=>
+        let (#read, #write) =
+            ::leptos::tachys::reactive_graph::bind::IntoSplitSignal::into_split_signal(#value);
+    };
+    let methods = quote! {
+        .#prop(#read)
+        .#on_change(::leptos::prelude::Callback::new(move |#new| {
+            ::leptos::prelude::Update::update(&#write, |#current| *#current = #new);
+        }))
+    };
+    (split_let, methods)
+}
+
 /// Converts children to tokens for use by components.
 ///
 /// The expansion is generally:
@@ -385,6 +1470,9 @@ pub(super) fn directive_to_any_attr_path(directive: &Directive) -> Option<TokenS
     let dir = &directive.dir;
     let path = match &*dir.to_string() {
         "class" | "style" => {
+            if let Some(toggles) = class_toggles_tokens(directive) {
+                return Some(toggles);
+            }
             // avoid making it string coloured
             let key = directive.key.to_unspanned_string();
             let value = directive.value.clone().unwrap_or_else(Value::new_true);
@@ -431,10 +1519,12 @@ pub(super) fn directive_to_any_attr_path(directive: &Directive) -> Option<TokenS
             }
         }
         "on" => {
-            let event_path = event_listener_event_path(directive);
-            let value = &directive.value;
+            if let Some(listener) = passive_listener_tokens(directive) {
+                return Some(listener);
+            }
+            let (event, handler, _) = event_listener_tokens(directive);
             quote! {
-                ::leptos::tachys::html::event::on(#event_path, #value)
+                ::leptos::tachys::html::event::on(#event, #handler)
             }
         }
         "use" => {
@@ -453,7 +1543,72 @@ pub(super) fn directive_to_any_attr_path(directive: &Directive) -> Option<TokenS
     Some(path)
 }
 
+/// Converts a `class=(...)` list into `class` attributes, which should be
+/// added with all the other directives.
+pub(super) fn component_class_list_paths(list: &ClassList) -> Vec<TokenStream> {
+    class_list_items(list)
+        .into_iter()
+        .map(|item| quote! { ::leptos::tachys::html::class::class(#item) })
+        .collect()
+}
+
+/// Converts a `style=(...)` list into a `style` attribute for each property,
+/// which should be added with all the other directives.
+pub(super) fn component_style_list_paths(list: &StyleList) -> Vec<TokenStream> {
+    list.entries()
+        .iter()
+        .map(|entry| {
+            let (property, value) = (entry.property().repr(), entry.value());
+            quote! { ::leptos::tachys::html::style::style((#property, #value)) }
+        })
+        .collect()
+}
+
 /// This should be added with all the other directives.
 ///
 /// Spread attrs are added as `.add_any_attr(expr)`.
 pub(super) fn component_spread_tokens(attr: &SpreadAttr) -> TokenStream { attr.expr().clone() }
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::{event_listener_tokens, key_filter_value};
+    use crate::ast::attribute::directive::Directive;
+
+    #[test]
+    fn once_is_inside_guards() {
+        let dir: Directive = parse_quote! { on:keydown:prevent:enter:self:once={f} };
+        let (_, handler, _) = event_listener_tokens(&dir);
+        let handler = handler.to_string().replace(' ', "");
+
+        let prevent = handler.find("ev.prevent_default();").unwrap();
+        let key = handler
+            .find("ifev.key().eq_ignore_ascii_case(\"Enter\"){")
+            .unwrap();
+        let target = handler.find("ifev.target()==ev.current_target(){").unwrap();
+        let take = handler.find("handler.take()").unwrap();
+        assert!(prevent < key && key < target && target < take, "{handler}");
+        assert!(!handler.contains("return"), "{handler}");
+    }
+
+    #[test]
+    fn key_filters() {
+        for (modifier, key) in [
+            ("s", "s"),
+            ("esc", "Escape"),
+            ("page_down", "PageDown"),
+            ("f12", "F12"),
+            ("digit7", "7"),
+        ] {
+            assert_eq!(key_filter_value(modifier).as_deref(), Some(key));
+        }
+        for modifier in ["f25", "digit", "digitx", "digit10", "shift_tab"] {
+            assert_eq!(key_filter_value(modifier), None, "{modifier}");
+        }
+
+        let dir: Directive = parse_quote! { on:keydown:1:prevent={f} };
+        assert_eq!(dir.modifiers[0], "digit1");
+        assert!(syn::parse2::<Directive>(quote::quote! { on:keydown:12={f} }).is_err());
+    }
+}
@@ -1,4 +1,6 @@
+use proc_macro2::{Span, TokenStream};
 use proc_macro_error2::{abort, emit_error};
+use quote::quote;
 use syn::{ext::IdentExt, parse_quote, spanned::Spanned};
 
 #[allow(clippy::doc_markdown)]
@@ -42,11 +44,11 @@ pub fn snake_case_to_upper_camel(ident: syn::Ident) -> syn::Ident {
     syn::Ident::new_raw(&new, ident.span())
 }
 
-pub fn emit_error_if_modifier(m: Option<&syn::Ident>) {
-    if let Some(modifier) = m {
+pub fn emit_error_if_modifier(modifiers: &[syn::Ident]) {
+    if let Some(modifier) = modifiers.first() {
         emit_error!(
             modifier.span(),
-            "unknown modifier: modifiers are only supported on `on:` directives"
+            "unknown modifier: modifiers are only supported on `on:` and `bind:` directives"
         );
     }
 }
@@ -69,11 +71,160 @@ pub fn turbofishify(mut path: syn::Path) -> syn::Path {
     path
 }
 
+/// The largest `EitherOfN` type provided by `either_of`.
+const MAX_EITHER_OF: usize = 16;
+
+/// Wraps the `index`th of `total` alternative views in an `Either` type so
+/// that each alternative has the same type.
+///
+/// Uses `Either` for 2 alternatives and `EitherOf3`-`EitherOf16` for up to 16
+/// alternatives. Any more than that falls back to converting each view to an
+/// `AnyView`.
+pub fn either_wrapped(index: usize, total: usize, view: TokenStream) -> TokenStream {
+    match total {
+        0 | 1 => view,
+        2 => {
+            let variant = if index == 0 { quote!(Left) } else { quote!(Right) };
+            quote! { ::leptos::either::Either::#variant(#view) }
+        }
+        3..=MAX_EITHER_OF => {
+            let ty = syn::Ident::new(&format!("EitherOf{total}"), Span::call_site());
+            let variant = syn::Ident::new(
+                &char::from(b'A' + u8::try_from(index).expect("index is at most 16")).to_string(),
+                Span::call_site(),
+            );
+            quote! { ::leptos::either::#ty::#variant(#view) }
+        }
+        _ => quote! { ::leptos::prelude::IntoAny::into_any(#view) },
+    }
+}
+
+/// Elements that never have a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose contents are text, not HTML.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
+/// Checks whether `html` is exactly one element, with no text, comments or
+/// whitespace around it.
+///
+/// This only scans the tags roughly, so it does not check whether the HTML is
+/// valid.
+pub fn is_single_root_element(html: &str) -> bool {
+    let mut rest = html;
+    let mut depth = 0_usize;
+    let mut roots = 0_usize;
+
+    while !rest.is_empty() {
+        let Some(after_lt) = rest.strip_prefix('<') else {
+            // text is only allowed inside the root element.
+            if depth == 0 {
+                return false;
+            }
+            rest = &rest[rest.find('<').unwrap_or(rest.len())..];
+            continue;
+        };
+
+        if let Some(comment) = after_lt.strip_prefix("!--") {
+            if depth == 0 {
+                return false;
+            }
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if let Some(closing) = after_lt.strip_prefix('/') {
+            let Some(new_depth) = depth.checked_sub(1) else {
+                return false;
+            };
+            depth = new_depth;
+            rest = closing.find('>').map_or("", |end| &closing[end + 1..]);
+        } else if after_lt.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            if depth == 0 {
+                roots += 1;
+            }
+            let name_end = after_lt
+                .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+                .unwrap_or(after_lt.len());
+            let name = after_lt[..name_end].to_ascii_lowercase();
+            let Some(tag_end) = open_tag_end(after_lt) else {
+                return false;
+            };
+            let self_closing = after_lt[..tag_end].ends_with('/');
+            rest = &after_lt[tag_end + 1..];
+
+            if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+                continue;
+            }
+            if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                // skip to the closing tag, ascii lowercasing keeps the indices.
+                let close = format!("</{name}");
+                rest = &rest[rest.to_ascii_lowercase().find(&close).unwrap_or(rest.len())..];
+            }
+            depth += 1;
+        } else {
+            // doctypes, processing instructions or a stray `<` in text.
+            if depth == 0 {
+                return false;
+            }
+            rest = after_lt;
+        }
+    }
+
+    roots == 1
+}
+
+/// Finds the index of the `>` that ends an opening tag, skipping over quoted
+/// attribute values.
+fn open_tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => (),
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use quote::{quote, ToTokens};
 
-    use super::turbofishify;
+    use super::{is_single_root_element, turbofishify};
+
+    #[test]
+    fn single_root_elements() {
+        for html in [
+            "<b>bold & <i>nested</i></b>",
+            "<hr/>",
+            "<br>",
+            "<div class=\"a > b\"><p>one<p>two</div>",
+            "<section><!-- <p> --><img src='x'></section>",
+            "<pre><script>if (a < b) { \"</p>\" }</script></pre>",
+        ] {
+            assert!(is_single_root_element(html), "{html}");
+        }
+    }
+
+    #[test]
+    fn not_single_root_elements() {
+        for html in [
+            "",
+            "just text",
+            "<p>one</p><p>two</p>",
+            "<p>one</p> ",
+            "\n<p>one</p>",
+            "<!-- comment --><p>one</p>",
+            "<br><br>",
+            "</p>",
+            "<p",
+        ] {
+            assert!(!is_single_root_element(html), "{html}");
+        }
+    }
 
     #[test]
     fn add_turbofish() {
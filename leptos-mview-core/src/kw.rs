@@ -5,3 +5,10 @@ syn::custom_keyword!(on);
 syn::custom_keyword!(prop);
 syn::custom_keyword!(clone);
 syn::custom_keyword!(slot);
+syn::custom_keyword!(key);
+syn::custom_keyword!(tag);
+syn::custom_keyword!(component);
+syn::custom_keyword!(fallback);
+syn::custom_keyword!(raw);
+syn::custom_keyword!(window);
+syn::custom_keyword!(document);
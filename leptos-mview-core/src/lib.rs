@@ -12,7 +12,7 @@ mod kw;
 mod parse;
 mod span;
 
-use ast::{Child, Children};
+use ast::{Child, Children, NodeChild};
 use expand::root_children_tokens;
 use proc_macro2::{Span, TokenStream};
 use proc_macro_error2::abort;
@@ -29,11 +29,14 @@ pub fn mview_impl(input: TokenStream) -> TokenStream {
         Ok(tree) => tree,
         Err(e) => return e.to_compile_error(),
     };
+    expand::emit_error_if_raw(children.node_children());
 
     // If there's a single top level component, can just expand like
     // div().attr(...).child(...)...
     // If there are multiple top-level children, need to use the fragment.
-    if children.len() == 1 {
+    // A lone `@let` is not a view, so also goes through the fragment.
+    let single_let = matches!(children.node_children().next(), Some(NodeChild::Let(_)));
+    if children.len() == 1 && !single_let {
         let child = children.into_vec().remove(0);
         match child {
             Child::Node(node) => quote! {
@@ -1,7 +1,10 @@
 //! Mini helper functions for parsing
 
 use proc_macro2::TokenStream;
-use syn::parse::{discouraged::Speculative, Parse, ParseBuffer, ParseStream};
+use syn::{
+    parse::{discouraged::Speculative, Parse, ParseBuffer, ParseStream},
+    Token,
+};
 
 pub fn extract_parenthesized(input: ParseStream) -> syn::Result<(syn::token::Paren, ParseBuffer)> {
     let stream;
@@ -94,3 +97,25 @@ where
 pub fn take_rest(input: ParseStream) -> TokenStream {
     TokenStream::parse(input).expect("parsing TokenStream should never fail")
 }
+
+/// Parses a single pattern inside closure pipes `|pat|`, returning the pattern
+/// without the pipes.
+///
+/// Like [`Element`](crate::ast::Element) children arguments, the pattern cannot
+/// contain a `|` outside of a nested group.
+pub fn closure_pat(input: ParseStream) -> syn::Result<TokenStream> {
+    let first_pipe = <Token![|]>::parse(input)?;
+    let mut tokens = TokenStream::new();
+    loop {
+        if rollback_err(input, <Token![|]>::parse).is_some() {
+            break Ok(tokens);
+        } else if let Some(tt) = rollback_err(input, proc_macro2::TokenTree::parse) {
+            tokens.extend([tt]);
+        } else {
+            break Err(syn::Error::new(
+                first_pipe.span,
+                "closure arguments not closed",
+            ));
+        }
+    }
+}
@@ -180,6 +180,55 @@ Classes/ids created with the selector syntax can be mixed with the attribute `cl
 
 There is also a special element `!DOCTYPE html;`, equivalent to `<!DOCTYPE html>`.
 
+HTML comments can be added with `!--` followed by a string literal. They are rendered as `<!--...-->` in SSR and created as comment nodes on the client. The text is inserted as-is, so add spaces inside the string if you want them around the comment text. Any `--` in the text is split with a space so that it can't end the comment early.
+
+```
+# use leptos_mview::mview; use leptos::prelude::*;
+mview! {
+    ul {
+        !-- " ko foreach: items "
+        li("item")
+        !-- " /ko "
+    }
+}
+# ;
+```
+
+### Dynamic tags
+
+If the tag of an element is only known at runtime, use `@tag(expr)` in place of the tag name. The expression can be anything that implements `AsRef<str>`. Selectors, attributes, directives and children are all still supported; as with web-components, other attributes are set without being checked.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let level = 2;
+let is_link = true;
+mview! {
+    @tag({format!("h{level}")}).title {
+        @tag({if is_link { "a" } else { "button" }}) href="/" ("home")
+    }
+}
+# ;
+```
+
+### Explicit components
+
+Tags are only treated as components if they start with an uppercase letter. To use a lowercase function as a component, prefix its path with `@component`. Components stored in a variable or returned from an expression can be used with `@component {expr}`.
+
+Closures or functions that take no props are also supported, and are just called.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+fn footer() -> impl IntoView {
+    mview! { footer("made with leptos") }
+}
+let heading = |text: &'static str| move || mview! { h1({text}) };
+mview! {
+    @component {heading("Hello!")};
+    @component footer;
+}
+# ;
+```
+
 ## Slots
 
 [Slots](https://docs.rs/leptos/latest/leptos/attr.slot.html) ([another example](https://github.com/leptos-rs/leptos/blob/main/examples/slots/src/lib.rs)) are supported by prefixing the struct with `slot:` inside the parent's children.
@@ -237,11 +286,10 @@ pub fn App() -> impl IntoView {
 There are (currently) 3 main types of values you can pass in:
 
 - **Literals** can be passed in directly to attribute values (like `data=3`, `class="main"`, `checked=true`).
-    - However, children do not accept literal numbers or bools - only strings.
-        ```compile_fail
-        # use leptos_mview::mview;
-        // does NOT compile.
-        mview! { p("this works " 0 " times: " true) }
+    - Number, bool and char literals in children are converted to strings at compile time.
+        ```
+        # use leptos_mview::mview; use leptos::prelude::*;
+        mview! { p("this works " 3 " times: " true) }
         # ;
         ```
 
@@ -285,27 +333,53 @@ There are (currently) 3 main types of values you can pass in:
     # ;
     ```
 
-    - Note that this always expands to `move || ...`: for any closures that take an argument, use the full closure block instead.
-        ```compile_error
-        # use leptos_mview::mview;
-        # use leptos::logging::log;
-        mview! {
-            input type="text" on:click=[log!("THIS DOESNT WORK")];
-        }
-        ```
-
-        Instead:
+    - On event handlers (`on:` directives), brackets expand to `move |_| ...` instead, as the handler takes the event as an argument. To use the event, name it with closure arguments before the brackets.
         ```
         # use leptos_mview::mview; use leptos::prelude::*;
         # use leptos::logging::log;
+        # let set_open = |_: bool| ();
         mview! {
-            input type="text" on:click={|_| log!("THIS WORKS!")};
+            button on:click=[set_open(true)]; // `{move |_| set_open(true)}`
+            input on:input=|ev|[log!("{}", event_target_value(&ev))];
         }
         # ;
         ```
 
 The bracketed values can also have some special prefixes for even more common shortcuts!
-- Currently, the only one is `f` - e.g. `f["{:.2}", stuff()]`. Adding an `f` will add `format!` into the closure. This is equivalent to `[format!("{:.2}", stuff())]` or `{move || format!("{:.2}", stuff())}`.
+- `f` - e.g. `f["{:.2}", stuff()]`. Adding an `f` will add `format!` into the closure. This is equivalent to `[format!("{:.2}", stuff())]` or `{move || format!("{:.2}", stuff())}`.
+- `s` - e.g. `s[count()]`, calls `.to_string()` inside the closure: `{move || count().to_string()}`.
+- `d` - e.g. `d[count() * 2]`, creates a derived signal: `{Signal::derive(move || count() * 2)}`.
+- `m` - e.g. `m[count() * 2]`, creates a memo: `{Memo::new(move |_| count() * 2)}`.
+- `c` - e.g. `c[|x: i32| x + 1]`, creates a callback from the closure inside: `{Callback::new(|x: i32| x + 1)}`.
+
+Any other prefix is treated as a function in scope, which is called with the closure: `my_prefix[count()]` expands to `{my_prefix(move || count())}`. This lets you (or other crates) add your own prefixes. Single letters are reserved for the built-in prefixes, so custom prefixes must be longer than one letter.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+fn upper(f: impl Fn() -> String + Send + Sync + 'static) -> Signal<String> {
+    Signal::derive(move || f().to_uppercase())
+}
+
+let name = RwSignal::new("leptos".to_string());
+mview! {
+    h1 title=s[name().len()] { upper[name()] }
+}
+# ;
+```
+
+Strings prefixed with a `$` are interpolated: any `{...}` inside the string is treated as a Rust expression. This is a shorthand for the `f` prefix, so `$"Hello {name()}!"` is the same as `f["Hello {}!", name()]`. If there are no expressions inside, it stays as a plain string. Use `{{` and `}}` for literal braces. Braces inside string and char literals in an expression are fine, but format specs like `{x:?}` are not supported: use the `f` prefix for those.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let name = RwSignal::new("leptos");
+let count = RwSignal::new(3);
+mview! {
+    p title=$"{name()}'s items" {
+        $"Hello {name()}, you have {count() * 2} items"
+    }
+}
+# ;
+```
 
 ## Attributes
 
@@ -366,12 +440,49 @@ mview! { LotsOfFlags wide=true tall=true red=false curvy=true count=3; }
 
 See also: [boolean attributes on HTML elements](#boolean-attributes-on-html-elements)
 
+### Class lists
+
+The `class` attribute can also be a list of classes in parentheses, instead of a long `f[...]` or many `class:` directives. Each entry is one of:
+- a string literal of classes that are always added, like `"btn btn-lg"`;
+- a conditional class `"name" => [condition]`, which is only added when the condition is true;
+- a block or bracketed value of space-separated classes, like `{extra_classes}` or `[theme()]`. Each class is added on its own, so when the value changes, only its old classes are removed and the others are kept. A block can be any value that implements `Display` and is only read once, so use a bracket for classes that change.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let is_active = RwSignal::new(true);
+let extra_classes = "rounded";
+mview! {
+    button class=("btn btn-lg", "active" => [is_active()], {extra_classes}) ("click")
+}
+# ;
+```
+
+This also works on components, where the classes are added to the component's elements like `class:` directives.
+
+
+### Style lists
+
+Similarly, the `style` attribute can be a list of `property: value` pairs in parentheses. Each property is a separate style, so it updates reactively on its own, unlike a `style` string. Properties are kebab-case identifiers, including custom properties like `--accent`.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let color = RwSignal::new("red");
+let accent = "blue";
+mview! {
+    p style=(color: [color()], margin-top: "4px", --accent: {accent}) ("styled")
+}
+# ;
+```
+
+This is the same as using a `style:` directive for each property, and also works on components.
+
+
 ### Directives
 
 Some special attributes (distinguished by the `:`) called **directives** have special functionality. All have the same behaviour as Leptos. These include:
 - `class:class-name=[when to show]`
 - `style:style-key=[style value]`
-- `on:event={move |ev| event handler}`
+- `on:event={move |ev| event handler}` or `on:event=|ev|[event handler]`
 - `prop:property-name={signal}`
 - `attr:name={value}`
 - `clone:ident_to_clone`
@@ -390,7 +501,7 @@ mview! {
 # ;
 ```
 
-The `class` and `style` directives also support using string literals, for more complicated names. Make sure the string for `class:` doesn't have spaces, or it will panic!
+The `class` and `style` directives also support using string literals, for more complicated names.
 
 ```
 # use leptos::prelude::*; use leptos_mview::mview;
@@ -402,8 +513,144 @@ mview! {
 # ;
 ```
 
+A `class:` string with spaces toggles each of the classes together. A bracketed condition is only run once for all of them, in a shared `Memo`.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let err = RwSignal::new(false);
+mview! {
+    p class:"bg-red-500 text-white"=[err()] ("something went wrong")
+}
+# ;
+```
+
 Note that the `use:` directive automatically calls `.into()` on its argument, consistent with behaviour from Leptos.
 
+### Event modifiers
+
+The `on:` directive supports chained modifiers after the event name, similar to Vue and Svelte:
+- `:prevent` calls `ev.prevent_default()` before the handler.
+- `:stop` calls `ev.stop_propagation()` before the handler.
+- `:self` only runs the handler if the event was dispatched on this element, not a child.
+- `:once` only runs the handler on the first event.
+- `:async` spawns the future returned by the handler with [`spawn_local`](https://docs.rs/leptos/latest/leptos/task/fn.spawn_local.html). A bracketed handler becomes an `async move` block, so `on:click:async=[save().await]` expands to `{move |_| { spawn_local(async move { save().await }); }}`.
+- `:value` and `:checked` pass [`event_target_value(&ev)`](https://docs.rs/leptos/latest/leptos/prelude/fn.event_target_value.html) or [`event_target_checked(&ev)`](https://docs.rs/leptos/latest/leptos/prelude/fn.event_target_checked.html) to the handler instead of the event, so a setter can be used directly. Adding `:parse` after `:value` calls `.parse()` on the value first, and ignores the event if it fails to parse.
+- `:capture` adds the listener for the capture phase.
+- `:passive` adds the listener with the `passive` option, so that it can't call `prevent_default()` and the browser doesn't wait for it while scrolling. Leptos does not support listener options yet, so the listener is added directly to the element (without delegation) when it is created, and removed when the view is cleaned up.
+- `:undelegated` does not delegate the event, same as Leptos.
+- `:ctrl`, `:shift`, `:alt` and `:meta` only run the handler if that key is held down.
+- On keyboard events (`keydown`, `keyup` and `keypress`), key names only run the handler if [`KeyboardEvent.key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key) matches, ignoring case. These can be single characters like `:s`, digits like `:1` (or `:digit1`), function keys like `:f2`, or named keys in snake case like `:enter`, `:tab`, `:escape` or `:page_down`. The aliases `:esc`, `:space`, `:del`, `:up`, `:down`, `:left` and `:right` are also supported.
+
+`:prevent`, `:stop`, `:self` and the key filters are run in the order they are written, so `on:click:self:prevent` only prevents clicks on the element itself, and `on:keydown:enter:prevent` only prevents the enter key.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let (count, set_count) = signal(0);
+let set_name = |name: String| leptos::logging::log!("{name}");
+mview! {
+    form on:submit:prevent=[set_count(count() + 1)] {
+        button on:click:once=|ev|[leptos::logging::log!("{:?}", ev)];
+    }
+    div on:scroll:passive={|_| ()};
+    textarea on:keydown:ctrl:s:prevent=[leptos::logging::log!("saved")];
+    input on:input:value={set_name};
+    input type="number" on:input:value:parse={set_count};
+}
+# ;
+```
+
+Async handlers can also be written directly as an `async` block or closure, which is the same as using `:async` with brackets:
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+# async fn save() {}
+mview! {
+    button on:click=async { save().await } ("save")
+    form on:submit:prevent=async |ev| {
+        leptos::logging::log!("{:?}", ev);
+        save().await;
+    };
+}
+# ;
+```
+
+### Lifecycle hooks
+
+`on:mount` and `on:cleanup` are pseudo-events on elements, which run the handler with the element once it is in the DOM, or when the view it is in is cleaned up. `on:unmount` is the same as `on:cleanup`. The element has its concrete `web_sys` type (`HtmlInputElement` for `input`), so this replaces creating a `NodeRef` with an `Effect` just to get the element.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+mview! {
+    input on:mount=|el|[_ = el.focus()];
+    div on:mount={|el| leptos::logging::log!("{}", el.offset_width())}
+        on:cleanup=|el|[leptos::logging::log!("removed {:?}", el.id())];
+}
+# ;
+```
+
+These only run in the browser, and do not take any modifiers.
+
+
+### Binding modifiers
+
+`bind:value` works on `input`, `textarea` and `select` elements, and also supports modifiers like Vue's `v-model`:
+- `:lazy` updates the signal on `change` instead of `input`.
+- `:trim` trims whitespace from the value.
+- `:number` parses the value into the signal's type, and only updates the signal if it parses.
+
+`bind:innerText` binds the text of a `contenteditable` element, and supports `:trim` and `:number` as well.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let name = RwSignal::new(String::new());
+let (age, set_age) = signal(0_u32);
+let bio = RwSignal::new(String::new());
+let color = RwSignal::new("red".to_string());
+mview! {
+    input bind:value:trim={name};
+    input type="number" bind:value:lazy:number={(age, set_age)};
+    textarea bind:value:lazy={bio};
+    select bind:value={color} {
+        option value="red"("Red")
+        option value="blue"("Blue")
+    }
+    p contenteditable="true" bind:innerText={bio};
+}
+# ;
+```
+
+
+### Binding components
+
+`bind:` also works on components, with one of two conventions:
+- `bind:prop={signal}` passes the signal to the prop as is, for components that take a signal like `RwSignal<T>`.
+- `bind:prop:split={signal}` splits the signal (an `RwSignal` or a `(getter, setter)` tuple) into its read and write halves. The read half is passed to `prop`, and a `Callback<T>` that sets the signal is passed to `on_prop_change`.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+#[component]
+fn Toggle(on: RwSignal<bool>) -> impl IntoView {
+    mview! { button on:click=[on.update(|on| *on = !*on)] ({on}) }
+}
+
+#[component]
+fn TextField(
+    #[prop(into)] value: Signal<String>,
+    on_value_change: Callback<String>,
+) -> impl IntoView {
+    mview! { input value={value} on:input:value=|v|[on_value_change.run(v)]; }
+}
+
+let enabled = RwSignal::new(false);
+let name = RwSignal::new(String::new());
+mview! {
+    Toggle bind:on={enabled};
+    TextField bind:value:split={name};
+}
+# ;
+```
+
+
 ## Children
 
 You may have noticed that the `let:data` prop was missing from the previous section on directive attributes!
@@ -423,7 +670,7 @@ mview! {
 # ;
 ```
 
-Note that you will usually need to add a `*` before the data you are using. If you forget that, rust-analyser will tell you to dereference here: `*{monkeys}`. This is obviously invalid - put it inside the braces.
+Note that you will usually need to add a `*` before the data you are using. If you forget that, rust-analyser will tell you to dereference here: `*{monkeys}`. This is obviously invalid - put it inside the braces. Alternatively, use [`@await`](#await), which gives you an owned value instead.
 
 Children can be wrapped in either braces or parentheses, whichever you prefer.
 
@@ -437,10 +684,190 @@ mview! {
 # ;
 ```
 
-Summary from the previous section on values in case you missed it: children can be literal strings (not bools or numbers!), blocks with Rust code inside (`{*monkeys}`), or the closure shorthand `[number() + 1]`.
+Summary from the previous section on values in case you missed it: children can be literals (strings, numbers, bools or chars), blocks with Rust code inside (`{*monkeys}`), or the closure shorthand `[number() + 1]`.
 
 Children with closures are also supported on slots.
 
+Children can be grouped together without a wrapping element using `_`, followed by the children in parentheses or braces. The group is expanded to a tuple, so it can be used anywhere a single child is expected. `clone:` directives can be added to the group, which only clone the values for the children inside the group.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let name = String::from("leptos");
+mview! {
+    Show when=[true] (
+        _ clone:name ("hello " strong({name}))
+    )
+}
+# ;
+```
+
+## Control flow
+
+Children prefixed with `@` are expanded to common control flow patterns, so that you don't need to write closures that end in `.into_any()`.
+
+### `@if`
+
+`@if` children are expanded to a reactive closure `move || if ... { ... } else { ... }`. Each branch is wrapped in an `Either` (or `EitherOf3`, `EitherOf4`, ...) so that branches can have different types without calling `.into_any()`.
+
+Conditions must be wrapped in brackets or braces, and children in parentheses or braces. The `@else` branch is optional.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let count = RwSignal::new(0);
+let name = Some("world");
+mview! {
+    @if [count() == 0] ("zero")
+    @else if [count() % 2 == 0] (strong("even"))
+    @else (em("odd"))
+
+    @if let Some(name) = {name} (
+        "hello " {name}
+    )
+}
+# ;
+```
+
+### `@for`
+
+`@for pat in [iter] (...)` iterates over a list. Everything between `for` and `in` is the pattern, so destructuring like `(i, item)` works.
+
+If a `key` is given, this expands to a keyed [`For`](https://docs.rs/leptos/latest/leptos/control_flow/fn.For.html) component. The key expression can use the pattern bindings, which are references to the item. `For` needs a closure or signal to iterate over, so use brackets `[...]` (or a signal in braces).
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+# #[derive(Clone)] struct Todo { id: u32, text: String }
+let todos = RwSignal::new(Vec::<Todo>::new());
+# let _owner = Owner::new(); _owner.with(|| {
+mview! {
+    ul {
+        @for todo in [todos.get()] key={todo.id} (
+            li({todo.text})
+        )
+    }
+}
+# ; });
+```
+
+Without a `key`, the iterator is mapped directly into views and collected with `.collect_view()`. Brackets make this reactive (the whole list is re-rendered on changes), while braces only render the list once.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let names = ["a", "b", "c"];
+mview! {
+    ol {
+        @for (i, name) in {names.into_iter().enumerate()} (
+            li { {i} ": " {name} }
+        )
+    }
+}
+# ;
+```
+
+### `@match`
+
+`@match [expr] { pat => (...), ... }` is expanded to a reactive closure containing a `match`. Like `@if`, each arm is wrapped in an `Either`/`EitherOfN` variant, and if there are more than 16 arms, each arm is converted to an `AnyView` instead.
+
+Match guards are allowed, and the commas between arms are optional.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+#[derive(Clone)]
+enum State { Loading, Error(String), Ready(u32) }
+
+let state = RwSignal::new(State::Loading);
+mview! {
+    @match [state.get()] {
+        State::Loading => ("loading..."),
+        State::Error(e) => (strong({e})),
+        State::Ready(0) => ("nothing here"),
+        State::Ready(n) if n > 100 => ("lots"),
+        State::Ready(n) => (span({n}) " items"),
+    }
+}
+# ;
+```
+
+### `@let`
+
+`@let pat = expr;` declares a binding that can be used by all the later siblings in the same children block, as well as their descendants. The expression does not need to be wrapped in brackets or braces, and runs up until the `;`.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let count = RwSignal::new(1);
+mview! {
+    @let doubled = move || count() * 2;
+    p { "count is " {count} }
+    p { "doubled is " {doubled} }
+}
+# ;
+```
+
+Everything after a `@let` is nested in a block together with the `let` statement, so bindings inside a component's children are re-evaluated whenever the children are rendered. Slots are not affected by `@let`s in their parent's children.
+
+### `@await`
+
+`@await {future} fallback=(...) |value| (...)` waits for a future before rendering its children, showing the optional `fallback` in the meantime. It is expanded to a `Suspense` that awaits a resource, which is what the `Await` component does, but with the fallback given to the `Suspense`.
+
+The resolved value is cloned out of the resource, so no dereferencing is needed, and it can be destructured. This means that the value must implement `Clone` as well as the serialization traits required by `Await`.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+# leptos::task::Executor::init_futures_executor().unwrap();
+async fn fetch_monkeys() -> (u32, String) { (3, "little monkeys".to_string()) }
+
+mview! {
+    @await {fetch_monkeys()} fallback=("loading...") |(count, what)| (
+        p({count} " " {what} ", jumping on the bed.")
+    )
+}
+# ;
+```
+
+### `@raw`
+
+`@raw` inserts unescaped HTML as a child. It takes a string literal, a block, or a bracket for HTML that changes reactively. **The HTML is not sanitized**, so only use this with trusted content.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+let rendered_markdown = "<h2>Some</h2><p><em>markdown</em></p>".to_string();
+mview! {
+    article {
+        h1("Post")
+        @raw(section) {rendered_markdown}
+        @raw "<hr/>"
+    }
+}
+# ;
+```
+
+Blocks and brackets can contain any HTML, so they are rendered inside a wrapper element with `inner_html`. This is a `div` by default, or the element named in parentheses, like `@raw(section)`.
+
+A string literal without a wrapper expands to tachys's `InertElement` instead, which has a few restrictions:
+- The HTML must have exactly one root element, with no text, comments or whitespace around it. This is checked when compiling.
+- Attributes cannot be added to it, so it cannot be used at the top level of the macro or directly inside a component's children (as the component could try to add attributes), including inside `@if`, `@match`, `@for`, `@await` or `_ (...)` at those places. Wrap it in an element or name a wrapper with `@raw(div) "..."` instead.
+
+### `@window` and `@document`
+
+Event listeners on the window or document can be added anywhere in the children with `@window` or `@document`, followed by any number of `on:` directives and a `;`. They are added when the view is created and removed when it is cleaned up, so there is no need to call `window_event_listener` and `on_cleanup` by hand.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+#[component]
+fn Dialog() -> impl IntoView {
+    let (open, set_open) = signal(true);
+    let (width, set_width) = signal(0.0);
+    mview! {
+        @window on:resize=[set_width(window().inner_width().unwrap().as_f64().unwrap())];
+        @document on:keydown:escape=[set_open(false)];
+        dialog open=[open()] { "width: " {width} }
+    }
+}
+```
+
+The event modifiers from [`on:`](#event-modifiers) are supported, except for `:capture`, `:passive` and `:undelegated`.
+
+`@window` uses [`window_event_listener`](https://docs.rs/leptos/latest/leptos/prelude/fn.window_event_listener.html), which only skips adding the listener on the server when rendering with SSR. `@document` listeners are added in an [`Effect`](https://docs.rs/leptos/latest/leptos/prelude/struct.Effect.html), so they are only added in the browser, after the view is mounted.
+
 # Extra details
 
 ## Kebab-case identifiers with attribute shorthand
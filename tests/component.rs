@@ -176,6 +176,32 @@ fn class_dir() {
     );
 }
 
+#[test]
+fn class_list_on_component() {
+    let yes = RwSignal::new(true);
+    let r = mview! {
+        TakesClass class=("one two", "three" => [yes.get()], "not-this" => false);
+    };
+    check_str(r, r#"<div class="takes-class one two three""#);
+
+    let r = mview! {
+        TakesClass class:"four five"=[yes.get()] class:"not this"=false;
+    };
+    check_str(r, r#"<div class="takes-class four five""#);
+}
+
+#[test]
+fn style_list_on_component() {
+    let size = RwSignal::new(2);
+    let r = mview! {
+        TakesClass style=(font-size: f["{}em", size.get()], --gap: "1px");
+    };
+    check_str(
+        r,
+        r#"<div class="takes-class" style="font-size:2em;--gap:1px;""#,
+    );
+}
+
 #[test]
 fn ids() {
     let r = mview! {
@@ -184,3 +210,89 @@ fn ids() {
 
     check_str(r, r#"<div id="id-1 id-number-two" class="i-take-ids""#)
 }
+
+mod helpers {
+    use leptos::prelude::*;
+    use leptos_mview::mview;
+
+    pub fn footer() -> impl IntoView {
+        mview! { footer { "made with " strong("leptos") } }
+    }
+}
+
+#[test]
+fn lowercase_component() {
+    let r = mview! {
+        @component helpers::footer.page-footer;
+    };
+    check_str(
+        r,
+        r#"<footer class="page-footer">made with <strong>leptos</strong></footer>"#,
+    );
+}
+
+#[test]
+fn component_expr() {
+    #[component]
+    fn Star(filled: bool) -> impl IntoView {
+        mview! { i.star class:filled={filled}; }
+    }
+    #[component]
+    fn Heart(filled: bool) -> impl IntoView {
+        mview! { i.heart class:filled={filled}; }
+    }
+
+    fn icon(heart: bool) -> fn(StarProps) -> AnyView {
+        if heart {
+            |props| {
+                Heart(HeartProps {
+                    filled: props.filled,
+                })
+                .into_any()
+            }
+        } else {
+            |props| Star(props).into_any()
+        }
+    }
+
+    let r = mview! {
+        @component {icon(true)} filled=true;
+    };
+    check_str(r, r#"<i class="filled heart"></i>"#);
+
+    // closures without props are called directly
+    let greeting = || mview! { b("hi") };
+    let r = mview! {
+        div { @component {greeting}; }
+    };
+    check_str(r, "<div><b>hi</b></div>");
+}
+
+#[test]
+fn bind_on_components() {
+    #[component]
+    fn Toggle(on: RwSignal<bool>) -> impl IntoView {
+        mview! { button aria-pressed=[on().to_string()]; }
+    }
+
+    #[component]
+    fn TextField(
+        #[prop(into)] value: Signal<String>,
+        #[prop(into)] on_value_change: Callback<String>,
+    ) -> impl IntoView {
+        mview! { input value={value} on:input:value=|v|[on_value_change.run(v)]; }
+    }
+
+    let on = RwSignal::new(true);
+    let name = RwSignal::new("name".to_string());
+    let (query, set_query) = signal("query".to_string());
+    let r = mview! {
+        Toggle bind:on={on};
+        TextField bind:value:split={name};
+        TextField bind:value:split={(query, set_query)};
+    };
+    check_str(
+        r,
+        r#"<button aria-pressed="true"></button><input value="name"><input value="query">"#,
+    );
+}
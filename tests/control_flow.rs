@@ -0,0 +1,235 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+mod utils;
+use utils::{check_str, Contains};
+
+#[test]
+fn if_else() {
+    let count = RwSignal::new(0);
+    let r = mview! {
+        div {
+            @if [count() == 0] ("zero")
+            @else if [count() % 2 == 0] (strong("even"))
+            @else (em("odd") " number")
+        }
+    };
+    check_str(
+        r,
+        Contains::AllOfNoneOf([&["<div>zero</div>"], &["even", "odd"]]),
+    );
+
+    count.set(3);
+    let r = mview! {
+        div {
+            @if [count() == 0] ("zero")
+            @else if [count() % 2 == 0] (strong("even"))
+            @else (em("odd") " number")
+        }
+    };
+    check_str(r, "<div><em>odd</em> number</div>");
+}
+
+#[test]
+fn if_let() {
+    let name = Some("world");
+    let r = mview! {
+        p {
+            "hello "
+            @if let Some(name) = {name} ({name})
+            @else ("nobody")
+        }
+    };
+    check_str(r, "<p>hello <!>world</p>");
+}
+
+#[test]
+fn if_without_else() {
+    let r = mview! {
+        span {
+            @if [false] ("not here")
+        }
+    };
+    check_str(r, Contains::AllOfNoneOf([&["<span>"], &["not here"]]));
+}
+
+#[test]
+fn many_else_ifs() {
+    let n = 12;
+    let r = mview! {
+        @if [n == 0] ("0")
+        @else if [n == 1] ("1")
+        @else if [n == 2] ("2")
+        @else if [n == 3] ("3")
+        @else if [n == 4] ("4")
+        @else if [n == 5] ("5")
+        @else if [n == 6] ("6")
+        @else if [n == 7] ("7")
+        @else if [n == 8] ("8")
+        @else if [n == 9] ("9")
+        @else if [n == 10] ("10")
+        @else if [n == 11] ("11")
+        @else if [n == 12] ("twelve")
+        @else if [n == 13] ("13")
+        @else if [n == 14] ("14")
+        @else if [n == 15] ("15")
+        @else ("more")
+    };
+    check_str(r, "twelve");
+}
+
+#[test]
+fn if_in_component() {
+    let r = mview! {
+        Show when=[true] (
+            @if [true] ("shown")
+        )
+    };
+    check_str(r, "shown");
+}
+
+#[test]
+fn for_keyed() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let items = RwSignal::new(vec![(1, "a"), (2, "b")]);
+        let r = mview! {
+            ul {
+                @for (id, name) in [items.get()] key={*id} (
+                    li id={id} ({name})
+                )
+            }
+        };
+        check_str(r, r#"<ul><li id="1">a</li><li id="2">b</li><!></ul>"#);
+    });
+}
+
+#[test]
+fn for_unkeyed() {
+    let r = mview! {
+        ol {
+            @for i in {1..=3} ( li({i * 2}) )
+        }
+    };
+    check_str(r, "<ol><li>2</li><li>4</li><li>6</li><!></ol>");
+
+    let names = RwSignal::new(vec!["x", "y"]);
+    let r = mview! {
+        div {
+            @for (i, name) in [names().into_iter().enumerate()] (
+                span({i}) {name}
+            )
+        }
+    };
+    check_str(r, "<div><span>0</span>x<span>1</span>y<!></div>");
+}
+
+#[test]
+fn match_arms() {
+    #[derive(Clone, Copy)]
+    enum State {
+        Loading,
+        Error(i32),
+        Ready(&'static str),
+    }
+
+    let state = RwSignal::new(State::Loading);
+    let view = move || {
+        mview! {
+            div {
+                @match [state.get()] {
+                    State::Loading => ("loading..."),
+                    State::Error(code) if code >= 500 => (strong("server error")),
+                    State::Error(code) => (em("error " {code})),
+                    State::Ready(data) => { p({data}) }
+                }
+            }
+        }
+    };
+    check_str(view(), "<div>loading...</div>");
+    state.set(State::Error(503));
+    check_str(view(), "<div><strong>server error</strong></div>");
+    state.set(State::Error(404));
+    check_str(view(), "<div><em>error <!>404</em></div>");
+    state.set(State::Ready("done"));
+    check_str(view(), "<div><p>done</p></div>");
+}
+
+#[test]
+fn match_many_arms() {
+    let n = 17;
+    let r = mview! {
+        @match {n} {
+            0 => ("0") 1 => ("1") 2 => ("2") 3 => ("3") 4 => ("4") 5 => ("5")
+            6 => ("6") 7 => ("7") 8 => ("8") 9 => ("9") 10 => ("10") 11 => ("11")
+            12 => ("12") 13 => ("13") 14 => ("14") 15 => ("15") 16 => ("16")
+            _ => ("many")
+        }
+    };
+    check_str(r, "many");
+}
+
+#[test]
+fn let_in_element() {
+    let count = RwSignal::new(3);
+    let r = mview! {
+        div {
+            span({count})
+            @let doubled = move || count() * 2;
+            @let (a, b) = ("a", "b");
+            strong({doubled})
+            em { {a} {b} {doubled} }
+        }
+    };
+    check_str(
+        r,
+        "<div><span>3</span><strong>6</strong><em>a<!>b<!>6</em></div>",
+    );
+}
+
+#[test]
+fn let_in_fragment() {
+    let r = mview! {
+        @let greeting = "hi";
+        p({greeting})
+        @if [true] (
+            @let name = "you";
+            {greeting} " " {name}
+        )
+    };
+    check_str(r, "<p>hi</p>hi<!> <!>you");
+}
+
+#[test]
+fn let_in_component() {
+    let name = String::from("world");
+    let r = mview! {
+        Show when=[true] (
+            @let name = name.clone();
+            @let len = name.len();
+            "hello " {name} {len}
+        )
+    };
+    check_str(r, "hello <!>world<!>5");
+}
+
+#[test]
+fn await_with_fallback() {
+    _ = leptos::task::Executor::init_futures_executor();
+    let owner = Owner::new();
+    owner.with(|| {
+        let r = mview! {
+            div {
+                @await {async { (3, String::from("monkeys")) }} fallback=("loading...") |(n, name)| (
+                    p({n} " little " {name})
+                )
+            }
+        };
+        check_str(r, "<div>loading...</div>");
+
+        // no fallback or pattern
+        let r = mview! {
+            @await {async {}} ("done")
+        };
+        check_str(r, Contains::Not("done"));
+    });
+}
@@ -0,0 +1,191 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+mod utils;
+use utils::check_str;
+
+#[test]
+fn bracket_handlers() {
+    let (open, set_open) = signal(false);
+    let (name, set_name) = signal(String::new());
+
+    let res = mview! {
+        div {
+            button on:click=[set_open(!open.get_untracked())] ("toggle")
+            input on:input=|ev|[set_name(event_target_value(&ev))] value=[name()];
+        }
+    };
+    check_str(res, r#"<div><button>toggle</button><input value=""></div>"#);
+}
+
+#[test]
+fn bracket_handlers_on_component() {
+    #[component]
+    fn Clickable() -> impl IntoView {
+        mview! {
+            button;
+        }
+    }
+
+    let (count, set_count) = signal(0);
+    let res = mview! {
+        Clickable on:click=[set_count(count.get_untracked() + 1)];
+        Clickable on:click=|ev|[ev.prevent_default()];
+    };
+    check_str(res, "<button></button><button></button>");
+}
+
+#[test]
+fn modifiers() {
+    let (count, set_count) = signal(0);
+
+    let res = mview! {
+        form on:submit:prevent:stop=[set_count(count.get_untracked() + 1)] {
+            button on:click:self:once={move |_| set_count(0)};
+            div on:scroll:passive={|_| ()} on:click:capture:stop=|ev|[ev.prevent_default()];
+        }
+    };
+    check_str(res, "<form><button></button><div></div></form>");
+}
+
+#[test]
+fn modifiers_on_component() {
+    #[component]
+    fn Form() -> impl IntoView {
+        mview! {
+            form;
+        }
+    }
+
+    let submitted = RwSignal::new(false);
+    let res = mview! {
+        Form
+            on:submit:prevent=[submitted.set(true)]
+            on:click:self:once={|ev| drop(ev)}
+            on:wheel:passive:capture=|ev|[submitted.set(ev.delta_y() > 0.0)];
+    };
+    check_str(res, "<form></form>");
+}
+
+#[test]
+fn key_filters() {
+    let (text, set_text) = signal(String::new());
+
+    let res = mview! {
+        input
+            on:keydown:enter:prevent=[set_text(String::new())]
+            on:keydown:ctrl:s=|ev|[ev.prevent_default()]
+            on:keyup:shift:tab={|_| ()}
+            on:keydown:esc:stop=|ev|[set_text(ev.key())]
+            on:click:ctrl:alt=[set_text("clicked".into())]
+            on:keydown:1:prevent=[set_text("1".into())]
+            on:keyup:alt:digit0=[set_text("0".into())]
+            value=[text()];
+    };
+    check_str(res, r#"<input value="">"#);
+}
+
+#[test]
+fn async_handlers() {
+    async fn save(value: i32) -> i32 { value }
+
+    let (count, set_count) = signal(0);
+
+    let res = mview! {
+        div {
+            button on:click=async { set_count(save(count.get_untracked()).await) } ("save")
+            button on:click:prevent=async move |ev| {
+                ev.stop_propagation();
+                save(1).await;
+            };
+            button on:click:async=[_ = save(2).await];
+            button on:click:async={|_| async { save(3).await; }};
+        }
+    };
+    check_str(
+        res,
+        "<div><button>save</button><button></button><button></button><button></button></div>",
+    );
+}
+
+#[test]
+fn value_modifiers() {
+    let (name, set_name) = signal(String::new());
+    let (flag, set_flag) = signal(false);
+    let (n, set_n) = signal(0_i32);
+
+    let res = mview! {
+        input on:input:value={set_name} value=[name()];
+        input type="checkbox" on:change:checked={set_flag} checked=[flag()];
+        input type="number" on:input:value:parse={set_n} value=[n()];
+        input on:input:value=|v|[set_name(v.to_uppercase())];
+        input on:input:value:parse:once=|n: i32|[set_n(n * 2)];
+        input on:input:prevent:checked:async={move |checked| async move { set_flag(checked) }};
+    };
+    check_str(
+        res,
+        r#"<input value=""><input type="checkbox"><input type="number" value="0"><input><input><input>"#,
+    );
+}
+
+#[test]
+fn lifecycle_hooks() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let (width, set_width) = signal(0);
+        let tag = "section";
+
+        let res = mview! {
+            div on:mount=|el|[set_width(el.offset_width())] {
+                input on:mount={|el| _ = el.focus()} on:cleanup={|el| _ = el.blur()};
+                svg { circle on:mount=|el|[el.set_id("c")]; }
+                my-widget on:mount={|el: leptos::web_sys::HtmlElement| el.set_title("hi")};
+                @tag({tag}) on:unmount=|el|[el.set_hidden(true)] ({width})
+            }
+        };
+        check_str(
+            res,
+            "<div><input><svg><circle></circle></svg><my-widget></my-widget><section>0</section></div>",
+        );
+    });
+}
+
+#[test]
+fn global_listeners() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let (width, set_width) = signal(0.0);
+        let (open, set_open) = signal(true);
+
+        // `window_event_listener` only skips adding the listener when there is an
+        // SSR context, so this can only be compiled in tests.
+        let _window = move || {
+            mview! {
+                @window on:resize=[set_width(1.0)] on:scroll:once={|_| ()};
+                p({width})
+            }
+        };
+
+        let res = mview! {
+            @document on:keydown:escape=[set_open(false)] on:visibilitychange:once={|_| ()};
+            div {
+                @document on:click:self=|ev|[leptos::logging::log!("{:?}", ev)];
+                p({open})
+            }
+        };
+        check_str(res, "<div><p>true</p></div>");
+    });
+}
+
+#[test]
+fn once_with_guards() {
+    let (count, set_count) = signal(0);
+
+    // `:once` is only used up by an event that passes the other guards, so a
+    // keydown that is not enter still leaves the handler in place.
+    let res = mview! {
+        input on:keydown:enter:once=[set_count(count.get_untracked() + 1)];
+        button on:click:self:prevent:once=|ev|[ev.stop_propagation()];
+        input on:keyup:ctrl:s:once:value={move |v: String| set_count(v.len() as i32)};
+    };
+    check_str(res, "<input><button></button><input>");
+}
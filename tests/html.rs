@@ -126,6 +126,54 @@ fn mixed_class_creation() {
     check_str(r, r#"class="some-class another-class always-here""#);
 }
 
+#[test]
+fn class_list() {
+    let active = RwSignal::new(true);
+    let extra = "extra";
+    let r = mview! {
+        button class=("btn btn-lg", "active" => [active()], "disabled" => false, {extra},);
+    };
+    check_str(r, r#"<button class="btn btn-lg active  extra"></button>"#);
+
+    let r = mview! {
+        span.always class=("a" => {active}, [active().to_string()], {String::from("b  c")});
+    };
+    check_str(r, r#"<span class="a true b  c always"></span>"#);
+}
+
+#[test]
+fn multi_class_toggles() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let err = RwSignal::new(true);
+        let big = true;
+        let r = mview! {
+            div
+                class:"bg-red-500 text-white"=[err()]
+                class:"  p-2   m-2 "={big}
+                class:"hidden invisible"=false
+                class:single=true;
+        };
+        check_str(
+            r,
+            r#"<div class="bg-red-500 text-white p-2 m-2   single"></div>"#,
+        );
+    });
+}
+
+#[test]
+fn style_list() {
+    let color = RwSignal::new("white");
+    let accent = "blue";
+    let r = mview! {
+        div style=(color: [color()], margin-top: "4px", --accent: {accent},);
+    };
+    check_str(
+        r,
+        r#"<div style="color:white;margin-top:4px;--accent:blue;"></div>"#,
+    );
+}
+
 #[test]
 fn custom_web_component() {
     let component = mview! {
@@ -140,6 +188,96 @@ fn custom_web_component() {
     );
 }
 
+#[test]
+fn svg_and_math() {
+    let result = mview! {
+        svg viewBox="0 0 10 10" {
+            circle cx="5" cy="5" r="4";
+        }
+        math { mi("x") }
+    };
+
+    check_str(
+        result,
+        r#"<svg viewBox="0 0 10 10"><circle cx="5" cy="5" r="4"></circle></svg><math><mi>x</mi></math>"#,
+    );
+}
+
+#[test]
+fn dynamic_tag() {
+    let level = 2;
+    let heading = format!("h{level}");
+    let is_link = true;
+    let component = mview! {
+        @tag({heading}).title #main-title data-level={level} class:big=[level < 3] {
+            @tag({if is_link { "a" } else { "button" }}) href="/" ("home")
+        }
+    };
+
+    check_str(
+        component,
+        r#"<h2 data-level="2" id="main-title" class="big title"><a href="/">home</a></h2>"#,
+    );
+}
+
+#[test]
+fn fragment_groups() {
+    let component = mview! {
+        div {
+            _ ("a" strong("b"))
+            _ ()
+            "c"
+        }
+    };
+    check_str(component, "<div>a<strong>b</strong><!>c</div>");
+
+    let name = String::from("me");
+    let component = mview! {
+        Show when=[true] {
+            _ clone:name ("hi " {name})
+        }
+    };
+    check_str(component, "hi <!>me");
+}
+
+#[test]
+fn raw_html() {
+    let html = RwSignal::new("<em>reactive</em>".to_string());
+    let component = mview! {
+        div {
+            "before "
+            @raw "<b>bold & <i>nested</i></b>"
+            @raw {String::from("<br/>")}
+            @raw [html.get()]
+        }
+    };
+    check_str(
+        component,
+        "<div>before <b>bold & <i>nested</i></b><div><br/></div><div><em>reactive</em></div></div>",
+    );
+}
+
+#[test]
+fn raw_html_multiple_roots() {
+    let markdown = "<h1>Title</h1>\n<p>one</p>\ntext".to_string();
+    let component = mview! {
+        @raw(article) {markdown}
+    };
+    check_str(
+        component,
+        "<article><h1>Title</h1>\n<p>one</p>\ntext</article>",
+    );
+
+    let component = mview! {
+        @raw [String::from("<p>a</p><p>b</p>")]
+        @raw(span) "<i>x</i> and <i>y</i>"
+    };
+    check_str(
+        component,
+        "<div><p>a</p><p>b</p></div><span><i>x</i> and <i>y</i></span>",
+    );
+}
+
 #[test]
 fn has_ref() {
     let node_ref = NodeRef::new();
@@ -165,6 +303,35 @@ fn bindings() {
     };
 }
 
+#[test]
+fn binding_modifiers() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let name = RwSignal::new(" padded ".to_string());
+        let (age, set_age) = signal(30_u8);
+        let price = RwSignal::new(1.5_f64);
+        let choice = RwSignal::new("b".to_string());
+        let notes = RwSignal::new("notes".to_string());
+        let content = RwSignal::new("editable".to_string());
+
+        let res = mview! {
+            input bind:value:trim={name};
+            input type="number" bind:value:number={(age, set_age)};
+            input bind:value:lazy:trim:number={price};
+            select bind:value={choice} {
+                option value="a"("A")
+                option value="b"("B")
+            }
+            textarea bind:value:lazy={notes};
+            div contenteditable="true" bind:innerText:trim={content};
+        };
+        check_str(
+            res,
+            r#"<input><input type="number"><input><select><option value="a">A</option><option value="b">B</option></select><textarea></textarea><div contenteditable="true"></div>"#,
+        );
+    });
+}
+
 #[test]
 fn doctype() {
     let doctype = mview! {
@@ -174,3 +341,21 @@ fn doctype() {
 
     check_str(doctype, "<!DOCTYPE html><div></div>");
 }
+
+#[test]
+fn comments() {
+    let result = mview! {
+        !-- "header"
+        div {
+            "a"
+            !-- " ko if: visible "
+            "b"
+            !-- "--> not -- closed -"
+        }
+    };
+
+    check_str(
+        result,
+        "<!--header--><div>a<!-- ko if: visible -->b<!-- - -> not - - closed - --></div>",
+    );
+}
@@ -0,0 +1,32 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn unknown_modifier() {
+    let name = RwSignal::new(String::new());
+    _ = mview! {
+        input bind:value:foo={name};
+    };
+}
+
+fn modifier_on_checked() {
+    let checked = RwSignal::new(false);
+    _ = mview! {
+        input type="checkbox" bind:checked:number={checked};
+    };
+}
+
+fn lazy_inner_text() {
+    let text = RwSignal::new(String::new());
+    _ = mview! {
+        div contenteditable="true" bind:innerText:lazy={text};
+    };
+}
+
+fn modifier_on_other_directive() {
+    let name = RwSignal::new(String::new());
+    _ = mview! {
+        input prop:value:trim={name};
+    };
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use leptos_mview::mview;
+
+fn not_a_string() {
+    let text = "hi";
+    _ = mview! {
+        div {
+            !-- {text}
+        }
+    };
+}
+
+fn missing_hyphen() {
+    _ = mview! {
+        div {
+            !- "comment"
+        }
+    };
+}
+
+fn main() {}
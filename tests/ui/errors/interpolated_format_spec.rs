@@ -0,0 +1,14 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn main() {
+    let count = 3;
+    _ = mview! {
+        p($"count: {count:?}")
+    };
+    // a `:` outside of delimiters, closure parameters and `::` paths is always
+    // treated as a format spec, even if it is not one.
+    _ = mview! {
+        p($"label: {'a: loop { break 'a count }}")
+    };
+}
@@ -0,0 +1,16 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn main() {
+    _ = mview! {
+        div { b"x" }
+    };
+
+    _ = mview! {
+        div { 300u8 " " 2147483648 " " 3px }
+    };
+
+    _ = mview! {
+        div { 1e40f32 }
+    };
+}
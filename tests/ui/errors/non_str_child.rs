@@ -1,7 +0,0 @@
-use leptos_mview::mview;
-
-fn main() {
-    mview! {
-        div { 3 }
-    };
-}
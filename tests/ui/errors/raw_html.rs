@@ -0,0 +1,74 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn top_level() {
+    _ = mview! {
+        @raw "<b>top</b>"
+    };
+}
+
+#[component]
+fn Wrapper(children: Children) -> impl IntoView {
+    mview! {
+        div { {children()} }
+    }
+}
+
+fn component_children() {
+    _ = mview! {
+        Wrapper { @raw "<b>direct child</b>" }
+    };
+}
+
+fn multiple_roots() {
+    _ = mview! {
+        article {
+            @raw "<p>one</p><p>two</p>"
+            @raw "plain text"
+            @raw " <hr/>"
+        }
+    };
+}
+
+fn after_let() {
+    _ = mview! {
+        @let html = "<b>after let</b>";
+        span({html})
+        @raw "<b>after let</b>"
+    };
+}
+
+fn control_flow_arms() {
+    let n = 1;
+    _ = mview! {
+        @if [n == 0] (@raw "<b>if</b>") @else (@raw "<b>else</b>")
+    };
+    _ = mview! {
+        @match {n} {
+            0 => (@raw "<b>match</b>"),
+            _ => ("other"),
+        }
+    };
+    _ = mview! {
+        @for i in {0..n} (@raw "<b>for</b>" {i})
+    };
+}
+
+fn fragment_children() {
+    _ = mview! {
+        _ (@raw "<b>fragment</b>")
+    };
+    _ = mview! {
+        Wrapper { _ (@raw "<b>fragment in component</b>") }
+    };
+}
+
+fn wrapped_is_allowed() {
+    let html = String::from("<p>one</p><p>two</p>");
+    _ = mview! {
+        @raw {html}
+        @raw(span) "<i>a</i> <i>b</i>"
+    };
+}
+
+fn main() {}
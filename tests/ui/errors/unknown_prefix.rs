@@ -0,0 +1,18 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn unknown_single_letter() {
+    let count = RwSignal::new(1);
+    _ = mview! {
+        p title=x[count()] { g[count()] }
+    };
+}
+
+fn missing_custom_prefix() {
+    let count = RwSignal::new(1);
+    _ = mview! {
+        p { uper[count()] }
+    };
+}
+
+fn main() {}
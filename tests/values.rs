@@ -19,3 +19,89 @@ fn f_value() {
     };
     check_str(r, r#"<input type="number" value="2.13""#);
 }
+
+#[test]
+fn literal_children() {
+    let r = mview! {
+        p("this works " 3 " times: " true)
+    };
+    check_str(r, "<p>this works <!>3<!> times: <!>true</p>");
+
+    let r = mview! {
+        span { 0x1F ' ' 1_000u32 ' ' 1e3 ' ' 0.5f32 ' ' 255u8 ' ' 2f32 }
+    };
+    check_str(
+        r,
+        "<span>31<!> <!>1000<!> <!>1000<!> <!>0.5<!> <!>255<!> <!>2</span>",
+    );
+}
+
+mod prefixes {
+    use leptos::prelude::*;
+
+    /// A custom prefix provided by another crate.
+    pub fn upper<T: ToString>(f: impl Fn() -> T + Send + Sync + 'static) -> Signal<String> {
+        Signal::derive(move || f().to_string().to_uppercase())
+    }
+}
+
+#[test]
+fn builtin_prefixes() {
+    #[component]
+    fn Takes(signal: Signal<i32>, memo: Memo<i32>, callback: Callback<i32, i32>) -> impl IntoView {
+        mview! { span({signal} " " {memo} " " {callback.run(3)}) }
+    }
+
+    let count = RwSignal::new(2);
+    let r = mview! {
+        Takes signal=d[count() + 1] memo=m[count() * 10] callback=c[move |x| x + count()];
+    };
+    check_str(r, "<span>3<!> <!>20<!> <!>5</span>");
+
+    let r = mview! {
+        p title=s[count() * 2] (s[count()] f["{}!", count()])
+    };
+    check_str(r, r#"<p title="4">2<!>2!</p>"#);
+}
+
+#[test]
+fn custom_prefix() {
+    use prefixes::upper;
+    let name = RwSignal::new("leptos");
+    let r = mview! {
+        div data-name=upper[name()] { upper[name()] }
+    };
+    check_str(r, r#"<div data-name="LEPTOS">LEPTOS</div>"#);
+}
+
+#[test]
+fn interpolated_strings() {
+    let name = RwSignal::new("leptos");
+    let count = RwSignal::new(3);
+    let r = mview! {
+        p title=$"{name()} ({count()})" {
+            $"Hello {name()}, you have {count() * 2} items {{ok}}"
+            $" and a static {{string}}"
+        }
+    };
+    check_str(
+        r,
+        r#"<p title="leptos (3)">Hello leptos, you have 6 items {ok}<!> and a static {string}</p>"#,
+    );
+}
+
+#[test]
+fn interpolated_string_literals() {
+    let name = RwSignal::new("leptos");
+    let r = mview! {
+        p {
+            $"{format!(\"}}{}{{\", name())} {'}'} {String::from(\"{\")} {u8::MAX}"
+        }
+    };
+    check_str(r, "<p>}leptos{ } { 255</p>");
+
+    let r = mview! {
+        p($"{(|x: i32| x * 2)(2)} {br#\"}\"#.len()} {b'}'} {std::cmp::max(1, 2)}")
+    };
+    check_str(r, "<p>4 1 125 2</p>");
+}